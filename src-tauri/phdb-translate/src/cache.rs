@@ -0,0 +1,170 @@
+use std::{
+  collections::HashMap,
+  fs,
+  io::ErrorKind,
+  path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+
+/// What a cached translation was asked for
+///
+/// The backend and the glossary version are part of the key, so switching backends or
+/// editing the glossary never returns a translation made under other conditions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct CacheKey {
+  pub text: String,
+  pub source_lang: String,
+  pub target_lang: String,
+  pub backend: String,
+  pub glossary_version: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheEntry {
+  #[serde(flatten)]
+  pub key: CacheKey,
+  pub translation: String,
+}
+
+/// Machine translations saved to a JSON file, so a name is only paid for once
+pub struct TranslationCache {
+  path: PathBuf,
+  entries: HashMap<CacheKey, String>,
+}
+
+impl TranslationCache {
+  /// read the cache at `path`, empty when there is no file yet
+  pub fn open(path: impl Into<PathBuf>) -> Result<Self> {
+    let path = path.into();
+    let entries = match fs::read_to_string(&path) {
+      Ok(text) => serde_json::from_str::<Vec<CacheEntry>>(&text)
+        .map_err(|e| Error::Cache(e.to_string()))?
+        .into_iter()
+        .map(|entry| (entry.key, entry.translation))
+        .collect(),
+      Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+      Err(e) => return Err(Error::SystemIO(e.to_string())),
+    };
+    Ok(Self { path, entries })
+  }
+
+  pub fn path(&self) -> &Path {
+    &self.path
+  }
+
+  pub fn len(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+
+  pub fn get(&self, key: &CacheKey) -> Option<&str> {
+    self.entries.get(key).map(String::as_str)
+  }
+
+  pub fn insert(&mut self, key: CacheKey, translation: String) {
+    self.entries.insert(key, translation);
+  }
+
+  /// every entry, sorted by key
+  pub fn entries(&self) -> Vec<CacheEntry> {
+    let mut entries = self
+      .entries
+      .iter()
+      .map(|(key, translation)| CacheEntry {
+        key: key.clone(),
+        translation: translation.clone(),
+      })
+      .collect::<Vec<_>>();
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    entries
+  }
+
+  pub fn save(&self) -> Result<()> {
+    if let Some(dir) = self.path.parent() {
+      fs::create_dir_all(dir).map_err(|e| Error::SystemIO(e.to_string()))?;
+    }
+    let json =
+      serde_json::to_string_pretty(&self.entries()).map_err(|e| Error::Cache(e.to_string()))?;
+    fs::write(&self.path, json).map_err(|e| Error::SystemIO(e.to_string()))
+  }
+
+  /// remove every entry, from the file too
+  pub fn clear(&mut self) -> Result<()> {
+    self.entries.clear();
+    self.save()
+  }
+
+  /// write the entries to a CSV file, one line per entry
+  pub fn export_csv(&self, path: impl AsRef<Path>) -> Result<()> {
+    let csv_error = |e: csv::Error| Error::Cache(e.to_string());
+    let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+    writer
+      .write_record([
+        "text",
+        "source_lang",
+        "target_lang",
+        "backend",
+        "glossary_version",
+        "translation",
+      ])
+      .map_err(csv_error)?;
+    for CacheEntry { key, translation } in self.entries() {
+      writer
+        .write_record([
+          &key.text,
+          &key.source_lang,
+          &key.target_lang,
+          &key.backend,
+          &key.glossary_version,
+          &translation,
+        ])
+        .map_err(csv_error)?;
+    }
+    writer.flush().map_err(|e| Error::SystemIO(e.to_string()))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn key(text: &str, backend: &str) -> CacheKey {
+    CacheKey {
+      text: text.to_string(),
+      source_lang: "ja".to_string(),
+      target_lang: "zh".to_string(),
+      backend: backend.to_string(),
+      glossary_version: "0123abcd".to_string(),
+    }
+  }
+
+  #[test]
+  fn save_and_reopen() {
+    let dir = std::env::temp_dir().join(format!("phdb-cache-{}", std::process::id()));
+    let path = dir.join("translation_cache.json");
+    let mut cache = TranslationCache::open(&path).unwrap();
+    assert!(cache.is_empty());
+    cache.insert(key("袖幅", "google"), "袖宽".to_string());
+    cache.save().unwrap();
+
+    let mut reopened = TranslationCache::open(&path).unwrap();
+    assert_eq!(reopened.get(&key("袖幅", "google")), Some("袖宽"));
+    assert_eq!(reopened.get(&key("袖幅", "deepl")), None);
+
+    let csv_path = dir.join("cache.csv");
+    reopened.export_csv(&csv_path).unwrap();
+    let csv = fs::read_to_string(&csv_path).unwrap();
+    assert!(csv.starts_with("text,source_lang,target_lang,backend,glossary_version,translation"));
+    assert!(csv.contains("袖幅,ja,zh,google,0123abcd,袖宽"));
+
+    reopened.clear().unwrap();
+    assert!(TranslationCache::open(&path).unwrap().is_empty());
+    fs::remove_dir_all(dir).unwrap();
+  }
+}
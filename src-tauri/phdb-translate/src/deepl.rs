@@ -0,0 +1,143 @@
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::{language::TargetLanguage, translator::Translator};
+
+pub const DEEPL_BASE_URL: &str = "https://api.deepl.com";
+/// the endpoint of the free plan, whose keys end with ":fx"
+pub const DEEPL_FREE_BASE_URL: &str = "https://api-free.deepl.com";
+
+#[derive(Deserialize)]
+struct DeepLSuccessResponse {
+  translations: Vec<DeepLTranslation>,
+}
+
+#[derive(Deserialize)]
+struct DeepLTranslation {
+  text: String,
+}
+
+#[derive(Deserialize)]
+struct DeepLFailedResponse {
+  message: String,
+}
+
+/// DeepL api v2, authenticated with an api key
+pub struct DeepLTranslator {
+  http_client: Client,
+  base_url: String,
+  api_key: String,
+}
+
+impl DeepLTranslator {
+  pub fn new(api_key: impl Into<String>) -> Self {
+    let api_key = api_key.into();
+    let base_url = if api_key.ends_with(":fx") {
+      DEEPL_FREE_BASE_URL
+    } else {
+      DEEPL_BASE_URL
+    };
+    Self {
+      http_client: Client::new(),
+      base_url: base_url.to_string(),
+      api_key,
+    }
+  }
+
+  /// send the requests to `base_url` instead of the endpoint of the key's plan
+  pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+    self.base_url = base_url.into();
+    self
+  }
+}
+
+#[async_trait]
+impl Translator for DeepLTranslator {
+  fn name(&self) -> &'static str {
+    "deepl"
+  }
+
+  async fn translate(&mut self, inputs: &[String], target: TargetLanguage) -> Result<Vec<String>> {
+    if inputs.is_empty() {
+      return Ok(Vec::new());
+    }
+    let resp = self
+      .http_client
+      .post(format!("{}/v2/translate", self.base_url))
+      .header("Authorization", format!("DeepL-Auth-Key {}", self.api_key))
+      .json(&serde_json::json!({
+        "text": inputs,
+        "source_lang": "JA",
+        "target_lang": target.deepl_code(),
+      }))
+      .send()
+      .await?;
+    if resp.status().as_u16() >= 300 {
+      let status = resp.status();
+      let message = match resp.json::<DeepLFailedResponse>().await {
+        Ok(failed) => failed.message,
+        Err(_) => status.to_string(),
+      };
+      return Err(Error::TranslateResponse(message));
+    }
+
+    let res: DeepLSuccessResponse = resp.json().await?;
+    let translated: Vec<String> = res.translations.into_iter().map(|t| t.text).collect();
+    if translated.len() != inputs.len() {
+      return Err(Error::TranslationCount {
+        expected: inputs.len(),
+        got: translated.len(),
+      });
+    }
+    Ok(translated)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use wiremock::matchers::{body_partial_json, header, method, path};
+  use wiremock::{Mock, MockServer, ResponseTemplate};
+
+  use super::*;
+
+  #[tokio::test]
+  async fn translate_against_mock_server() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+      .and(path("/v2/translate"))
+      .and(header("authorization", "DeepL-Auth-Key test-key"))
+      .and(body_partial_json(serde_json::json!({
+        "text": ["袖幅", "肩線"],
+        "target_lang": "ZH-HANS"
+      })))
+      .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "translations": [
+          {"detected_source_language": "JA", "text": "袖宽"},
+          {"detected_source_language": "JA", "text": "肩线"}
+        ]
+      })))
+      .mount(&server)
+      .await;
+
+    let mut translator = DeepLTranslator::new("test-key").with_base_url(server.uri());
+    let inputs = ["袖幅", "肩線"].map(String::from);
+    assert_eq!(
+      translator
+        .translate(&inputs, TargetLanguage::ZhHans)
+        .await
+        .unwrap(),
+      ["袖宽", "肩线"]
+    );
+
+    let mut unauthorized = DeepLTranslator::new("wrong").with_base_url(server.uri());
+    assert!(matches!(
+      unauthorized
+        .translate(&inputs, TargetLanguage::ZhHans)
+        .await,
+      Err(Error::TranslateResponse(_))
+    ));
+    assert_eq!(DeepLTranslator::new("key:fx").base_url, DEEPL_FREE_BASE_URL);
+  }
+}
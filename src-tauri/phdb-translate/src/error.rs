@@ -6,8 +6,14 @@ pub enum Error {
     TranslateApiCall(#[from] reqwest::Error),
     #[error("translate got error response:{0}")]
     TranslateResponse(String),
+    #[error("translate got {got} results for {expected} inputs")]
+    TranslationCount { expected: usize, got: usize },
     #[error("system IO error: {0}")]
     SystemIO(String),
+    #[error("translation cache error: {0}")]
+    Cache(String),
+    #[error("unsupported language: {0}")]
+    UnsupportedLanguage(String),
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+use gcp_auth::{AuthenticationManager, Token};
+use reqwest::Client;
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::{language::TargetLanguage, translator::Translator};
+
+pub const GOOGLE_BASE_URL: &str = "https://translation.googleapis.com";
+const TRANSLATE_PATH: &str = "/v3/projects/phdb-translate/locations/us-central1:translateText";
+const GLOSSARY: &str = "projects/phdb-translate/locations/us-central1/glossaries/phdb-glossary1";
+const SCOPES: &[&str] = &["https://www.googleapis.com/auth/cloud-platform"];
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleTranslateSuccessResponse {
+  #[serde(default)]
+  translations: Vec<Translation>,
+  #[serde(default)]
+  glossary_translations: Vec<Translation>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Translation {
+  translated_text: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GoogleTranslateFailedResponse {
+  error: ErrorMessage,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorMessage {
+  message: String,
+}
+
+/// Google Cloud Translation v3 with the phdb glossary
+///
+/// The phdb glossary is Japanese to Simplified Chinese, so other languages are
+/// translated without it.
+///
+/// Authenticates with the service account of `GOOGLE_APPLICATION_CREDENTIALS`.
+///
+/// [WARN] the api cannot be reached from china mainland
+pub struct GoogleTranslator {
+  http_client: Client,
+  base_url: String,
+  /// used instead of the service account when set
+  access_token: Option<String>,
+  gcp_token: Option<Token>,
+  auth_manager: Option<AuthenticationManager>,
+}
+
+impl Default for GoogleTranslator {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl GoogleTranslator {
+  pub fn new() -> Self {
+    Self {
+      http_client: Client::new(),
+      base_url: GOOGLE_BASE_URL.to_string(),
+      access_token: None,
+      gcp_token: None,
+      auth_manager: None,
+    }
+  }
+
+  /// send the requests to `base_url` instead of [`GOOGLE_BASE_URL`]
+  pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+    self.base_url = base_url.into();
+    self
+  }
+
+  pub fn with_access_token(mut self, access_token: impl Into<String>) -> Self {
+    self.access_token = Some(access_token.into());
+    self
+  }
+
+  async fn ensure_token(&mut self) -> Result<String> {
+    if let Some(access_token) = &self.access_token {
+      return Ok(access_token.clone());
+    }
+    if self.gcp_token.is_none() || self.auth_manager.is_none() {
+      // 初回取得またはトークンが無効な場合
+      let auth_manager = AuthenticationManager::new().await?;
+      let token = auth_manager.get_token(SCOPES).await?;
+      self.gcp_token = Some(token);
+      self.auth_manager = Some(auth_manager);
+    }
+    Ok(self.gcp_token.as_ref().unwrap().as_str().to_string())
+  }
+
+  pub async fn refresh_token(&mut self) -> Result<()> {
+    let auth_manager = match &mut self.auth_manager {
+      Some(am) => am,
+      None => {
+        let am = AuthenticationManager::new().await?;
+        self.auth_manager = Some(am);
+        self.auth_manager.as_mut().unwrap()
+      }
+    };
+    let token = auth_manager.get_token(SCOPES).await?;
+    self.gcp_token = Some(token);
+    Ok(())
+  }
+}
+
+#[async_trait]
+impl Translator for GoogleTranslator {
+  fn name(&self) -> &'static str {
+    "google"
+  }
+
+  async fn translate(&mut self, inputs: &[String], target: TargetLanguage) -> Result<Vec<String>> {
+    if inputs.is_empty() {
+      return Ok(Vec::new());
+    }
+    // トークンがまだ取得されていない場合、ここで取得
+    let token_str = self.ensure_token().await?;
+
+    let with_glossary = target == TargetLanguage::ZhHans;
+    let mut translate_request_data = serde_json::json!(
+        {
+          "sourceLanguageCode": "ja",
+          "targetLanguageCode": target.code(),
+          "contents": inputs,
+        }
+    );
+    if with_glossary {
+      translate_request_data["glossaryConfig"] = serde_json::json!({ "glossary": GLOSSARY });
+    }
+    let resp = self
+      .http_client
+      .post(format!("{}{TRANSLATE_PATH}", self.base_url))
+      .header("Content-Type", "application/json; charset=utf-8")
+      .json(&translate_request_data)
+      .bearer_auth(token_str)
+      .send()
+      .await?;
+    if resp.status().as_u16() >= 300 {
+      let resp_message: GoogleTranslateFailedResponse = resp.json().await?;
+      return Err(Error::TranslateResponse(resp_message.error.message));
+    }
+
+    let res: GoogleTranslateSuccessResponse = resp.json().await?;
+    let translations = if with_glossary {
+      res.glossary_translations
+    } else {
+      res.translations
+    };
+    let translated: Vec<String> = translations
+      .into_iter()
+      .map(|t| t.translated_text)
+      .collect();
+    if translated.len() != inputs.len() {
+      return Err(Error::TranslationCount {
+        expected: inputs.len(),
+        got: translated.len(),
+      });
+    }
+    Ok(translated)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use wiremock::matchers::{body_partial_json, header, method, path};
+  use wiremock::{Mock, MockServer, ResponseTemplate};
+
+  use super::*;
+
+  #[tokio::test]
+  async fn translate_against_mock_server() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+      .and(path(TRANSLATE_PATH))
+      .and(header("authorization", "Bearer test-token"))
+      .and(body_partial_json(serde_json::json!({"contents": ["袖幅"]})))
+      .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "glossaryTranslations": [{"translatedText": "袖宽"}]
+      })))
+      .mount(&server)
+      .await;
+    Mock::given(method("POST"))
+      .and(path(TRANSLATE_PATH))
+      .and(body_partial_json(serde_json::json!({
+        "contents": ["袖丈"],
+        "targetLanguageCode": "ko"
+      })))
+      .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "translations": [{"translatedText": "소매 길이"}]
+      })))
+      .mount(&server)
+      .await;
+    Mock::given(method("POST"))
+      .and(path(TRANSLATE_PATH))
+      .and(body_partial_json(
+        serde_json::json!({"contents": ["エラー"]}),
+      ))
+      .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+        "error": {"message": "bad request"}
+      })))
+      .mount(&server)
+      .await;
+
+    let mut translator = GoogleTranslator::new()
+      .with_base_url(server.uri())
+      .with_access_token("test-token");
+    let translated = translator
+      .translate(&["袖幅".to_string()], TargetLanguage::ZhHans)
+      .await
+      .unwrap();
+    assert_eq!(translated, ["袖宽"]);
+    let translated = translator
+      .translate(&["袖丈".to_string()], TargetLanguage::Ko)
+      .await
+      .unwrap();
+    assert_eq!(translated, ["소매 길이"]);
+    assert!(matches!(
+      translator.translate(&["エラー".to_string()], TargetLanguage::ZhHans).await,
+      Err(Error::TranslateResponse(message)) if message == "bad request"
+    ));
+  }
+}
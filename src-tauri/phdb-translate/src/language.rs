@@ -0,0 +1,90 @@
+use std::{fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+
+/// A language the size tables are translated into
+///
+/// Written as its code, such as "zh-TW", in the config, the cache and file names.
+#[derive(
+  Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize,
+)]
+pub enum TargetLanguage {
+  /// Simplified Chinese, the only language of the built-in glossary
+  #[default]
+  #[serde(rename = "zh")]
+  ZhHans,
+  /// Traditional Chinese, for Taiwan
+  #[serde(rename = "zh-TW")]
+  ZhHant,
+  #[serde(rename = "ko")]
+  Ko,
+  #[serde(rename = "en")]
+  En,
+}
+
+impl TargetLanguage {
+  pub const ALL: [Self; 4] = [Self::ZhHans, Self::ZhHant, Self::Ko, Self::En];
+
+  /// code of the language, also used by Google Cloud Translation
+  pub fn code(self) -> &'static str {
+    match self {
+      Self::ZhHans => "zh",
+      Self::ZhHant => "zh-TW",
+      Self::Ko => "ko",
+      Self::En => "en",
+    }
+  }
+
+  /// code of the language for the `target_lang` of DeepL
+  pub fn deepl_code(self) -> &'static str {
+    match self {
+      Self::ZhHans => "ZH-HANS",
+      Self::ZhHant => "ZH-HANT",
+      Self::Ko => "KO",
+      Self::En => "EN-US",
+    }
+  }
+}
+
+impl fmt::Display for TargetLanguage {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.code())
+  }
+}
+
+impl FromStr for TargetLanguage {
+  type Err = Error;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.trim().to_ascii_lowercase().as_str() {
+      "zh" | "zh-cn" | "zh-hans" => Ok(Self::ZhHans),
+      "zh-tw" | "zh-hant" => Ok(Self::ZhHant),
+      "ko" => Ok(Self::Ko),
+      "en" => Ok(Self::En),
+      other => Err(Error::UnsupportedLanguage(other.to_string())),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_and_serialize_codes() {
+    for language in TargetLanguage::ALL {
+      assert_eq!(language.code().parse::<TargetLanguage>().unwrap(), language);
+      assert_eq!(
+        serde_json::to_string(&language).unwrap(),
+        format!("\"{}\"", language.code())
+      );
+    }
+    assert_eq!(
+      "zh-Hant".parse::<TargetLanguage>().unwrap(),
+      TargetLanguage::ZhHant
+    );
+    assert!("fr".parse::<TargetLanguage>().is_err());
+  }
+}
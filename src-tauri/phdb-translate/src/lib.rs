@@ -1,11 +1,20 @@
+mod cache;
+mod deepl;
 mod error;
+mod google;
+mod language;
+mod translator;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+pub use cache::{CacheEntry, CacheKey, TranslationCache};
+pub use deepl::{DeepLTranslator, DEEPL_BASE_URL, DEEPL_FREE_BASE_URL};
 pub use error::Error;
-use gcp_auth::{AuthenticationManager, Token};
+pub use google::{GoogleTranslator, GOOGLE_BASE_URL};
+pub use language::TargetLanguage;
 use reqwest::Client;
-use serde::Deserialize;
+use sha2::{Digest, Sha256};
+pub use translator::{GlossaryOnly, Translator};
 
 use crate::error::Result;
 
@@ -15,133 +24,197 @@ mod embedded_glossary {
   include!(concat!(env!("OUT_DIR"), "/glossary.rs"));
 }
 
-const TRANSLATE_URL: &str =
-  "https://translation.googleapis.com/v3/projects/phdb-translate/locations/us-central1:translateText";
 pub struct TranslateClient {
-  gcp_token: Option<Token>,
-  http_client: Client,
-  auth_manager: Option<AuthenticationManager>,
+  translator: Box<dyn Translator>,
+  /// the built-in glossary, to Simplified Chinese
   glossary: HashMap<String, String>,
+  /// entries kept by the user for each language, checked before `glossary`
+  user_glossary: HashMap<TargetLanguage, HashMap<String, String>>,
+  /// results of the remote backends, checked before calling them
+  cache: Option<TranslationCache>,
 }
 
-const SCOPES: &[&str] = &["https://www.googleapis.com/auth/cloud-platform"];
+const SOURCE_LANG: &str = "ja";
+
 const GLOSSARY_URL: &str =
   "https://size-table-generator.s3.ap-northeast-1.amazonaws.com/phdb-glossary.csv";
 
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct GoogleTranslateSuccessResponse {
-  glossary_translations: Vec<Translation>,
-}
-
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct Translation {
-  translated_text: String,
-}
-
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct GoogleTranslateFailedResponse {
-  error: ErrorMessage,
-}
-
-#[derive(Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct ErrorMessage {
-  message: String,
+/// The result of [`TranslateClient::translate_local`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LocalTranslation {
+  /// one per input, the input itself when the glossary has no entry for it
+  pub translated: Vec<String>,
+  /// inputs the glossary has no entry for, in input order
+  pub missing: Vec<String>,
 }
 
 impl TranslateClient {
+  /// the embedded glossary, with Google Cloud Translation for [`TranslateClient::translate`]
   pub async fn new() -> Result<Self> {
     let http_client = reqwest::Client::new();
     let glossary = Self::read_glossary_file(&http_client).await?;
     Ok(Self {
-      gcp_token: None,
-      http_client,
-      auth_manager: None,
+      translator: Box::new(GoogleTranslator::new()),
       glossary,
+      user_glossary: HashMap::new(),
+      cache: None,
     })
   }
 
-  async fn ensure_token(&mut self) -> Result<&Token> {
-    if self.gcp_token.is_none() || self.auth_manager.is_none() {
-      // 初回取得またはトークンが無効な場合
-      let auth_manager = AuthenticationManager::new().await?;
-      let token = auth_manager.get_token(SCOPES).await?;
-      self.gcp_token = Some(token);
-      self.auth_manager = Some(auth_manager);
+  /// `glossary` as the built-in glossary instead of the glossary file, with
+  /// [`GlossaryOnly`] until [`TranslateClient::set_translator`]
+  pub fn with_glossary(glossary: HashMap<String, String>) -> Self {
+    Self {
+      translator: Box::new(GlossaryOnly),
+      glossary,
+      user_glossary: HashMap::new(),
+      cache: None,
     }
-    Ok(self.gcp_token.as_ref().unwrap())
   }
 
-  pub async fn refresh_token(&mut self) -> Result<()> {
-    let auth_manager = match &mut self.auth_manager {
-      Some(am) => am,
-      None => {
-        let am = AuthenticationManager::new().await?;
-        self.auth_manager = Some(am);
-        self.auth_manager.as_mut().unwrap()
-      }
-    };
-    let token = auth_manager.get_token(SCOPES).await?;
-    self.gcp_token = Some(token);
-    Ok(())
+  /// use `translator` for every following [`TranslateClient::translate`]
+  pub fn set_translator(&mut self, translator: Box<dyn Translator>) {
+    self.translator = translator;
+  }
+
+  /// keep the results of remote backends in `cache`
+  pub fn set_cache(&mut self, cache: TranslationCache) {
+    self.cache = Some(cache);
+  }
+
+  pub fn cache(&self) -> Option<&TranslationCache> {
+    self.cache.as_ref()
+  }
+
+  pub fn cache_mut(&mut self) -> Option<&mut TranslationCache> {
+    self.cache.as_mut()
   }
 
-  pub fn translate_local(&mut self, inputs: &[String]) -> Result<Vec<String>> {
-    let mut translated = Vec::new();
+  /// name of the machine translation backend, such as "google"
+  pub fn translator_name(&self) -> &'static str {
+    self.translator.name()
+  }
+
+  /// translate the inputs with the glossary of `target` only, keeping the inputs it has
+  /// no entry for
+  pub fn translate_local(
+    &mut self,
+    inputs: &[String],
+    target: TargetLanguage,
+  ) -> Result<LocalTranslation> {
+    let mut translation = LocalTranslation::default();
     for input in inputs {
-      if self.glossary.contains_key(input) {
-        translated.push(self.glossary[input].clone());
-      } else {
-        translated.push(input.clone());
+      match self.lookup(input, target) {
+        Some(zh) => translation.translated.push(zh.clone()),
+        None => {
+          translation.translated.push(input.clone());
+          translation.missing.push(input.clone());
+        }
       }
     }
-    Ok(translated)
+    Ok(translation)
   }
-  /// translate the input text to zh
+
+  /// replace the user entries of `target`, which override and extend the built-in glossary
+  pub fn set_user_glossary(&mut self, target: TargetLanguage, entries: HashMap<String, String>) {
+    self.user_glossary.insert(target, entries);
+  }
+
+  /// the translation of the built-in glossary, ignoring user entries
   ///
-  /// this functions rely on google cloud translate api
+  /// the built-in glossary only has Simplified Chinese, so other languages have none
+  pub fn builtin_translation(&self, input: &str, target: TargetLanguage) -> Option<&str> {
+    self
+      .builtin_glossary(target)?
+      .get(input)
+      .map(String::as_str)
+  }
+
+  fn builtin_glossary(&self, target: TargetLanguage) -> Option<&HashMap<String, String>> {
+    (target == TargetLanguage::ZhHans).then_some(&self.glossary)
+  }
+
+  fn lookup(&self, input: &str, target: TargetLanguage) -> Option<&String> {
+    self
+      .user_glossary
+      .get(&target)
+      .and_then(|entries| entries.get(input))
+      .or_else(|| self.builtin_glossary(target)?.get(input))
+  }
+
+  /// short hash of the glossary content of `target`
   ///
-  /// [WARN] so it will not work in china mainland
-  pub async fn translate(&mut self, inputs: &[String]) -> Result<Vec<String>> {
-    // トークンがまだ取得されていない場合、ここで取得
-    let token_str = {
-      let token = self.ensure_token().await?;
-      token.as_str().to_string()
-    };
+  /// changes whenever an entry is added or edited, user entries included, so saved
+  /// results can tell which glossary they were translated with
+  pub fn glossary_version(&self, target: TargetLanguage) -> String {
+    let builtin = self
+      .builtin_glossary(target)
+      .into_iter()
+      .flat_map(HashMap::keys);
+    let user = self
+      .user_glossary
+      .get(&target)
+      .into_iter()
+      .flat_map(HashMap::keys);
+    let entries = builtin
+      .chain(user)
+      .filter_map(|ja| Some((ja, self.lookup(ja, target)?)));
+    version_of(entries)
+  }
 
-    let translate_request_data = serde_json::json!(
-        {
-          "sourceLanguageCode": "ja",
-          "targetLanguageCode": "zh",
-          "contents": inputs,
-            "glossaryConfig": {
-            "glossary":"projects/phdb-translate/locations/us-central1/glossaries/phdb-glossary1"
-          }
-        }
-    );
-    let resp = self
-      .http_client
-      .post(TRANSLATE_URL)
-      .header("Content-Type", "application/json; charset=utf-8")
-      .json(&translate_request_data)
-      .bearer_auth(token_str)
-      .send()
-      .await?;
-    if resp.status().as_u16() >= 300 {
-      let resp_message: GoogleTranslateFailedResponse = resp.json().await?;
-      return Err(Error::TranslateResponse(resp_message.error.message));
+  /// short hash of the built-in glossary of `target` alone, the version cached machine
+  /// translations are keyed by
+  ///
+  /// the backends only know the built-in glossary, so accepting a translation into the
+  /// user glossary keeps the cache valid
+  fn builtin_glossary_version(&self, target: TargetLanguage) -> String {
+    version_of(self.builtin_glossary(target).into_iter().flatten())
+  }
+
+  /// translate the input text to `target` with the machine translation backend
+  ///
+  /// inputs found in the cache are not sent again, and new results are added to it
+  pub async fn translate(
+    &mut self,
+    inputs: &[String],
+    target: TargetLanguage,
+  ) -> Result<Vec<String>> {
+    if self.cache.is_none() || !self.translator.is_remote() {
+      return self.translator.translate(inputs, target).await;
     }
+    let backend = self.translator.name();
+    let glossary_version = self.builtin_glossary_version(target);
+    let key = |text: &String| CacheKey {
+      text: text.clone(),
+      source_lang: SOURCE_LANG.to_string(),
+      target_lang: target.code().to_string(),
+      backend: backend.to_string(),
+      glossary_version: glossary_version.clone(),
+    };
 
-    let res: GoogleTranslateSuccessResponse = resp.json().await?;
+    let cache = self.cache.as_ref().unwrap();
+    let mut seen = HashSet::new();
+    let uncached = inputs
+      .iter()
+      .filter(|input| cache.get(&key(input)).is_none() && seen.insert(*input))
+      .cloned()
+      .collect::<Vec<_>>();
+    let translated = self.translator.translate(&uncached, target).await?;
 
+    let cache = self.cache.as_mut().unwrap();
+    let added = !uncached.is_empty();
+    for (input, translation) in uncached.iter().zip(translated) {
+      cache.insert(key(input), translation);
+    }
+    if added {
+      if let Err(e) = cache.save() {
+        tracing::warn!("translation cache not saved: {e}");
+      }
+    }
     Ok(
-      res
-        .glossary_translations
-        .into_iter()
-        .map(|t| t.translated_text)
+      inputs
+        .iter()
+        .map(|input| cache.get(&key(input)).unwrap_or(input).to_string())
         .collect(),
     )
   }
@@ -186,9 +259,71 @@ impl TranslateClient {
   }
 }
 
+/// short hash of glossary `entries`, whatever order they come in
+fn version_of<'a>(entries: impl Iterator<Item = (&'a String, &'a String)>) -> String {
+  let mut entries = entries.collect::<Vec<_>>();
+  entries.sort();
+  entries.dedup();
+  let mut hasher = Sha256::new();
+  for (ja, zh) in entries {
+    hasher.update(ja.as_bytes());
+    hasher.update(b"\t");
+    hasher.update(zh.as_bytes());
+    hasher.update(b"\n");
+  }
+  hasher
+    .finalize()
+    .iter()
+    .take(8)
+    .map(|b| format!("{b:02x}"))
+    .collect()
+}
+
 #[cfg(test)]
 mod tests {
-  use crate::TranslateClient;
+  use std::collections::HashMap;
+
+  use wiremock::matchers::{body_partial_json, method, path};
+  use wiremock::{Mock, MockServer, ResponseTemplate};
+
+  use crate::{DeepLTranslator, TargetLanguage, TranslateClient, TranslationCache};
+
+  #[tokio::test]
+  async fn cached_terms_are_not_sent_again() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+      .and(path("/v2/translate"))
+      .and(body_partial_json(serde_json::json!({"text": ["袖幅"]})))
+      .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "translations": [{"text": "소매 폭"}]
+      })))
+      .expect(1)
+      .mount(&server)
+      .await;
+    let cache_path =
+      std::env::temp_dir().join(format!("phdb-client-cache-{}.json", std::process::id()));
+    let client = |cache| {
+      let mut client = TranslateClient::with_glossary(HashMap::new());
+      client.set_translator(Box::new(
+        DeepLTranslator::new("test-key").with_base_url(server.uri()),
+      ));
+      client.set_cache(cache);
+      client
+    };
+    let inputs = ["袖幅".to_string()];
+
+    let mut first = client(TranslationCache::open(&cache_path).unwrap());
+    for _ in 0..2 {
+      let translated = first.translate(&inputs, TargetLanguage::Ko).await.unwrap();
+      assert_eq!(translated, ["소매 폭"]);
+    }
+    // the results are saved, so a new run does not send them either
+    let mut second = client(TranslationCache::open(&cache_path).unwrap());
+    let translated = second.translate(&inputs, TargetLanguage::Ko).await.unwrap();
+    assert_eq!(translated, ["소매 폭"]);
+    std::fs::remove_file(&cache_path).unwrap();
+  }
+
   /// need env GOOGLE_APPLICATION_CREDENTIALS
   #[tokio::test]
   async fn it_works() {
@@ -209,8 +344,12 @@ mod tests {
       "重量(g):225",
       "前身頃:57",
     ];
-    let glossary_path = std::env::var("GLOSSARY_PATH").unwrap();
-    let resp = client.translate(&inputs).await.unwrap();
+    let _glossary_path = std::env::var("GLOSSARY_PATH").unwrap();
+    let inputs = inputs.into_iter().map(String::from).collect::<Vec<_>>();
+    let resp = client
+      .translate(&inputs, TargetLanguage::ZhHans)
+      .await
+      .unwrap();
     resp.iter().for_each(|s| println!("{s}"));
   }
 }
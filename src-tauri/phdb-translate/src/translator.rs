@@ -0,0 +1,38 @@
+use async_trait::async_trait;
+
+use crate::{error::Result, language::TargetLanguage};
+
+/// A machine translation backend, asked for the terms the glossary has no entry for
+#[async_trait]
+pub trait Translator: Send {
+  /// short name of the backend, such as "google"
+  fn name(&self) -> &'static str;
+
+  /// whether results come from an api, and are worth caching
+  fn is_remote(&self) -> bool {
+    true
+  }
+
+  /// translate the inputs from Japanese to `target`, one result per input in input order
+  async fn translate(&mut self, inputs: &[String], target: TargetLanguage) -> Result<Vec<String>>;
+}
+
+/// The backend for when no translation api may be used: every input comes back as is,
+/// so only the glossary translates
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlossaryOnly;
+
+#[async_trait]
+impl Translator for GlossaryOnly {
+  fn name(&self) -> &'static str {
+    "glossary"
+  }
+
+  fn is_remote(&self) -> bool {
+    false
+  }
+
+  async fn translate(&mut self, inputs: &[String], _: TargetLanguage) -> Result<Vec<String>> {
+    Ok(inputs.to_vec())
+  }
+}
@@ -0,0 +1,95 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use ab_glyph::{Font, FontRef};
+use sha2::{Digest, Sha256};
+
+// PDFにはTrueTypeアウトラインしか埋め込めないので、OTF(CFF)ではなくTTFを使う
+// ブランチではなくリリースタグを指定し、ビルドごとに別のフォントにならないようにする
+// 韓国語の表も描けるよう、ハングルのない地域別サブセットではなく Pan-CJK 版を使う
+const FONT_URL: &str =
+  "https://github.com/notofonts/noto-cjk/raw/Sans2.004/Sans/Variable/TTF/NotoSansCJKsc-VF.ttf";
+const FONT_FILE_NAME: &str = "NotoSansCJKsc.ttf";
+/// 埋め込むフォントに必ず含まれているべき文字
+const REQUIRED_CHARS: &str = "尺码表尺碼表品番肩宽사이즈표품번Size Chart No.0123456789.~cm";
+
+fn main() {
+  println!("cargo:rerun-if-env-changed=STG_FONT_PATH");
+  println!("cargo:rerun-if-env-changed=STG_FONT_SHA256");
+  embed_font();
+}
+
+/// 画像とPDFに埋め込むCJKフォントを用意する
+///
+/// `STG_FONT_PATH` が設定されていればそのファイルを使い、なければダウンロードする。
+/// フォントが用意できなければ、画像とPDFを出力できないバイナリにならないようビルドを失敗させる
+fn embed_font() {
+  let out_dir = env::var("OUT_DIR").unwrap();
+  let font_path = PathBuf::from(&out_dir).join(FONT_FILE_NAME);
+  let font_code_path = PathBuf::from(&out_dir).join("font.rs");
+
+  let bytes = match env::var("STG_FONT_PATH") {
+    Ok(local_path) => {
+      println!("cargo:rerun-if-changed={}", local_path);
+      fs::read(&local_path)
+        .unwrap_or_else(|e| panic!("Failed to read font from {}: {}", local_path, e))
+    }
+    Err(_) => match fs::read(&font_path) {
+      Ok(bytes) => bytes,
+      Err(_) => download_font(),
+    },
+  };
+  verify_font(&bytes);
+  fs::write(&font_path, &bytes).expect("Failed to write font file");
+  write_font_code(&font_code_path, &font_path);
+}
+
+fn download_font() -> Vec<u8> {
+  println!("cargo:warning=Downloading font file from {}", FONT_URL);
+  reqwest::blocking::get(FONT_URL)
+    .and_then(|response| response.error_for_status())
+    .and_then(|response| response.bytes())
+    .map(|bytes| bytes.to_vec())
+    .unwrap_or_else(|e| {
+      panic!(
+        "Failed to download font file: {}. Set STG_FONT_PATH to a local TrueType font to build offline.",
+        e
+      )
+    })
+}
+
+/// 壊れたファイルやエラーページを埋め込まないよう、フォントとして読めて必要な文字があるか確かめる
+///
+/// `STG_FONT_SHA256` が設定されていれば、内容がそのハッシュと一致することも確かめる
+fn verify_font(bytes: &[u8]) {
+  if let Ok(expected) = env::var("STG_FONT_SHA256") {
+    let actual = Sha256::digest(bytes)
+      .iter()
+      .map(|b| format!("{b:02x}"))
+      .collect::<String>();
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+      panic!(
+        "Font sha256 mismatch: expected {}, got {}",
+        expected.trim(),
+        actual
+      );
+    }
+  }
+  let font = FontRef::try_from_slice(bytes).expect("The font file is not a TrueType font");
+  let missing = REQUIRED_CHARS
+    .chars()
+    .filter(|&c| font.glyph_id(c).0 == 0)
+    .collect::<String>();
+  if !missing.is_empty() {
+    panic!("The font has no glyph for: {}", missing);
+  }
+}
+
+fn write_font_code(path: &Path, font_path: &Path) {
+  let code = format!(
+    "pub fn get_font_data() -> &'static [u8] {{\n    include_bytes!({:?})\n}}\n",
+    font_path.display().to_string()
+  );
+  fs::write(path, code).expect("Failed to write font code");
+}
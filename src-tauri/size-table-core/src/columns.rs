@@ -0,0 +1,209 @@
+use std::{collections::HashMap, fmt};
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{Error, Result};
+
+/// The logical columns the pipeline reads from a sheet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Column {
+  ItemCode,
+  SizeCode,
+  SizeText,
+}
+
+impl Column {
+  pub const ALL: [Column; 3] = [Column::ItemCode, Column::SizeCode, Column::SizeText];
+
+  /// the canonical header, as written in the original sheets
+  pub fn label(self) -> &'static str {
+    match self {
+      Column::ItemCode => "品番",
+      Column::SizeCode => "SZ",
+      Column::SizeText => "採寸",
+    }
+  }
+}
+
+impl fmt::Display for Column {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.label())
+  }
+}
+
+/// Header spellings accepted for each logical column
+///
+/// Sheets from other departments name the columns differently, so every field takes a
+/// list of aliases. Headers are compared after trimming, ignoring ascii case.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ColumnAliases {
+  pub item_code: Vec<String>,
+  pub size_code: Vec<String>,
+  pub size_text: Vec<String>,
+}
+
+impl Default for ColumnAliases {
+  fn default() -> Self {
+    let aliases = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+    Self {
+      item_code: aliases(&["品番", "商品番号", "品番コード", "商品コード"]),
+      size_code: aliases(&["SZ", "サイズ", "サイズコード", "Size"]),
+      size_text: aliases(&["採寸", "採寸(cm)", "採寸（cm）", "寸法", "実寸"]),
+    }
+  }
+}
+
+impl ColumnAliases {
+  pub fn aliases(&self, column: Column) -> &[String] {
+    match column {
+      Column::ItemCode => &self.item_code,
+      Column::SizeCode => &self.size_code,
+      Column::SizeText => &self.size_text,
+    }
+  }
+
+  /// the logical column a header cell stands for, if any
+  pub fn column_of(&self, header: impl AsRef<str>) -> Option<Column> {
+    let header = header.as_ref().trim();
+    Column::ALL.into_iter().find(|&column| {
+      self
+        .aliases(column)
+        .iter()
+        .any(|alias| alias.trim().eq_ignore_ascii_case(header))
+    })
+  }
+}
+
+/// Where each logical column sits in the sheet, whatever the column order is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnIndices {
+  pub item_code: usize,
+  pub size_code: usize,
+  pub size_text: usize,
+}
+
+impl ColumnIndices {
+  /// map every header cell to its logical column
+  ///
+  /// Fails with [`Error::DuplicateColumn`] when two headers stand for the same column,
+  /// and with [`Error::MissingColumns`] listing every column no header stands for.
+  pub fn detect<S: AsRef<str>>(
+    header: impl IntoIterator<Item = S>,
+    aliases: &ColumnAliases,
+  ) -> Result<Self> {
+    let mut found: HashMap<Column, Vec<(usize, String)>> = HashMap::new();
+    for (i, cell) in header.into_iter().enumerate() {
+      if let Some(column) = aliases.column_of(&cell) {
+        found
+          .entry(column)
+          .or_default()
+          .push((i, cell.as_ref().trim().to_string()));
+      }
+    }
+
+    for column in Column::ALL {
+      if let Some(headers) = found.get(&column).filter(|headers| headers.len() > 1) {
+        return Err(Error::DuplicateColumn {
+          column,
+          headers: headers.clone(),
+        });
+      }
+    }
+    let missing = Column::ALL
+      .into_iter()
+      .filter(|column| !found.contains_key(column))
+      .collect_vec();
+    if !missing.is_empty() {
+      return Err(Error::MissingColumns(missing));
+    }
+
+    let index = |column| found[&column][0].0;
+    Ok(Self {
+      item_code: index(Column::ItemCode),
+      size_code: index(Column::SizeCode),
+      size_text: index(Column::SizeText),
+    })
+  }
+}
+
+impl ColumnIndices {
+  pub fn get(&self, column: Column) -> usize {
+    match column {
+      Column::ItemCode => self.item_code,
+      Column::SizeCode => self.size_code,
+      Column::SizeText => self.size_text,
+    }
+  }
+}
+
+/// the spreadsheet letters of a 0 based column index: A, B, .., Z, AA, ..
+pub fn column_name(mut i: usize) -> String {
+  let mut name = Vec::new();
+  loop {
+    name.push(b'A' + (i % 26) as u8);
+    if i < 26 {
+      break;
+    }
+    i = i / 26 - 1;
+  }
+  name.reverse();
+  String::from_utf8(name).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn match_header_aliases() {
+    let aliases = ColumnAliases::default();
+    assert_eq!(aliases.column_of(" 商品番号 "), Some(Column::ItemCode));
+    assert_eq!(aliases.column_of("size"), Some(Column::SizeCode));
+    assert_eq!(aliases.column_of("採寸(cm)"), Some(Column::SizeText));
+    assert_eq!(aliases.column_of("色番"), None);
+
+    let custom: ColumnAliases = serde_json::from_str(r#"{"sizeText": ["寸法表"]}"#).unwrap();
+    assert_eq!(custom.column_of("寸法表"), Some(Column::SizeText));
+    assert_eq!(custom.column_of("採寸"), None);
+    assert_eq!(custom.column_of("品番"), Some(Column::ItemCode));
+  }
+
+  #[test]
+  fn detect_columns_in_any_order() {
+    let aliases = ColumnAliases::default();
+    let indices = ColumnIndices::detect(["色番", "採寸", "SZ", "品番"], &aliases).unwrap();
+    assert_eq!(
+      indices,
+      ColumnIndices {
+        item_code: 3,
+        size_code: 2,
+        size_text: 1,
+      }
+    );
+
+    match ColumnIndices::detect(["品番", "SZ", "サイズ", "採寸"], &aliases) {
+      Err(Error::DuplicateColumn { column, headers }) => {
+        assert_eq!(column, Column::SizeCode);
+        assert_eq!(headers, [(1, "SZ".to_string()), (2, "サイズ".to_string())]);
+      }
+      other => panic!("unexpected {other:?}"),
+    }
+    match ColumnIndices::detect(["SZ"], &aliases) {
+      Err(Error::MissingColumns(missing)) => {
+        assert_eq!(missing, [Column::ItemCode, Column::SizeText])
+      }
+      other => panic!("unexpected {other:?}"),
+    }
+  }
+
+  #[test]
+  fn spreadsheet_column_names() {
+    assert_eq!(column_name(0), "A");
+    assert_eq!(column_name(25), "Z");
+    assert_eq!(column_name(26), "AA");
+    assert_eq!(column_name(701), "ZZ");
+    assert_eq!(column_name(702), "AAA");
+  }
+}
@@ -0,0 +1,141 @@
+use std::{
+  collections::BTreeMap,
+  fs::File,
+  io::{BufReader, ErrorKind},
+  path::PathBuf,
+};
+
+use phdb_translate::{
+  DeepLTranslator, GlossaryOnly, GoogleTranslator, TargetLanguage, TranslationCache, Translator,
+};
+use serde::Deserialize;
+
+use crate::{
+  columns::ColumnAliases,
+  excel_processor::{DuplicatePolicy, ProcessOptions},
+  size_order::SizeOrder,
+  synonyms::MeasurementSynonyms,
+  units::InchOptions,
+  Error, Result,
+};
+
+pub const APP_IDENTIFIER: &str = "Size Table Generator";
+
+/// `config.json` in the app config directory, shared by the app and `stg`
+///
+/// Every field is optional, missing ones keep their default.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Config {
+  pub deepl_api_key: String,
+  pub column_aliases: ColumnAliases,
+  pub duplicate_policy: DuplicatePolicy,
+  pub size_order: SizeOrder,
+  /// canonical measurement name -> other spellings, added to the built-in table
+  pub measurement_synonyms: BTreeMap<String, Vec<String>>,
+  /// machine translate measurement names missing from the glossary
+  pub machine_translation: bool,
+  /// the backend of machine translation
+  pub translator: TranslatorOptions,
+  /// inches added to the exported tables
+  pub inch: InchOptions,
+  /// languages of the tables, such as `["zh", "zh-TW"]`, Simplified Chinese when empty
+  pub languages: Vec<TargetLanguage>,
+}
+
+/// Which api translates the measurement names missing from the glossary
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranslatorBackend {
+  #[default]
+  Google,
+  /// needs `deeplApiKey`
+  DeepL,
+  /// no api, names missing from the glossary stay in Japanese
+  Glossary,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TranslatorOptions {
+  pub backend: TranslatorBackend,
+  /// replaces the Google endpoint, for a proxy or a local mock server
+  pub google_base_url: Option<String>,
+  /// replaces the DeepL endpoint, which otherwise follows the plan of the key
+  pub deepl_base_url: Option<String>,
+}
+
+impl Config {
+  /// the backend chosen in `translator`
+  pub fn translator(&self) -> Result<Box<dyn Translator>> {
+    let options = &self.translator;
+    Ok(match options.backend {
+      TranslatorBackend::Google => {
+        let mut google = GoogleTranslator::new();
+        if let Some(base_url) = &options.google_base_url {
+          google = google.with_base_url(base_url);
+        }
+        Box::new(google)
+      }
+      TranslatorBackend::DeepL => {
+        if self.deepl_api_key.is_empty() {
+          return Err(Error::MissingDeepLApiKey);
+        }
+        let mut deepl = DeepLTranslator::new(&self.deepl_api_key);
+        if let Some(base_url) = &options.deepl_base_url {
+          deepl = deepl.with_base_url(base_url);
+        }
+        Box::new(deepl)
+      }
+      TranslatorBackend::Glossary => Box::new(GlossaryOnly),
+    })
+  }
+
+  pub fn process_options(&self) -> ProcessOptions {
+    ProcessOptions {
+      column_aliases: self.column_aliases.clone(),
+      duplicate_policy: self.duplicate_policy,
+      size_order: self.size_order.clone(),
+      measurement_synonyms: MeasurementSynonyms::with_extra(&self.measurement_synonyms),
+      machine_translation: self.machine_translation,
+      languages: self.languages.clone(),
+      ..Default::default()
+    }
+  }
+}
+
+/// `<config dir>/Size Table Generator`
+pub fn config_dir() -> Result<PathBuf> {
+  let mut base = dirs::config_dir()
+    .ok_or_else(|| std::io::Error::new(ErrorKind::NotFound, "Config directory not found"))?;
+  base.push(APP_IDENTIFIER);
+  Ok(base)
+}
+
+/// `<data dir>/Size Table Generator/translation_cache.json`, machine translations kept
+/// between runs
+pub fn translation_cache_path() -> Result<PathBuf> {
+  let mut base = dirs::data_dir()
+    .ok_or_else(|| std::io::Error::new(ErrorKind::NotFound, "Data directory not found"))?;
+  base.push(APP_IDENTIFIER);
+  base.push("translation_cache.json");
+  Ok(base)
+}
+
+pub fn open_translation_cache() -> Result<TranslationCache> {
+  Ok(TranslationCache::open(translation_cache_path()?)?)
+}
+
+/// read `config.json`, falling back to the defaults when there is none
+pub fn load_config() -> Result<Config> {
+  let mut path = config_dir()?;
+  path.push("config");
+  path.set_extension("json");
+  let file = match File::open(path) {
+    Ok(file) => file,
+    Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Config::default()),
+    Err(e) => return Err(e.into()),
+  };
+
+  Ok(serde_json::from_reader(BufReader::new(file))?)
+}
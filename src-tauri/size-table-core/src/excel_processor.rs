@@ -0,0 +1,807 @@
+use std::{
+  collections::{HashMap, HashSet},
+  path::Path,
+};
+
+use calamine::DataType;
+use itertools::Itertools;
+use melrose_types::{ItemCode, SizeCode};
+use phdb_translate::{TargetLanguage, TranslateClient};
+use serde::{Deserialize, Serialize};
+
+use crate::columns::{Column, ColumnAliases, ColumnIndices};
+use crate::input::InputWorkbook;
+use crate::labels::size_header;
+use crate::models::{ItemInfo, ItemMeta, ItemTable};
+use crate::size_order::SizeOrder;
+use crate::size_parser::SizeDetails;
+use crate::synonyms::MeasurementSynonyms;
+use crate::units::is_length;
+use crate::validation::{
+  untranslated_terms, DuplicateRows, MeasurementMismatch, MissingMeasurements, RowIssue,
+  UntranslatedTerm, ValidationReport,
+};
+use crate::{Error, Result};
+
+/// shown in a table cell when a size does not list the measurement
+pub const MISSING_CELL: &str = "-";
+
+/// Settings of a processing run, usually built from [`crate::Config`]
+#[derive(Debug, Clone, Default)]
+pub struct ProcessOptions {
+  pub column_aliases: ColumnAliases,
+  pub sheets: SheetSelection,
+  pub duplicate_policy: DuplicatePolicy,
+  pub size_order: SizeOrder,
+  pub measurement_synonyms: MeasurementSynonyms,
+  /// send measurement names missing from the glossary to the translation api
+  pub machine_translation: bool,
+  /// one table per item for each language, Simplified Chinese when empty
+  pub languages: Vec<TargetLanguage>,
+}
+
+impl ProcessOptions {
+  /// the languages of the run, without repeats
+  pub fn languages(&self) -> Vec<TargetLanguage> {
+    if self.languages.is_empty() {
+      return vec![TargetLanguage::default()];
+    }
+    self.languages.iter().copied().unique().collect()
+  }
+}
+
+/// Which row is used when one (品番, SZ) pair has rows with different [採寸]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicatePolicy {
+  #[default]
+  FirstWins,
+  LastWins,
+  /// stop the run with an error naming the conflicting rows
+  Fail,
+}
+
+/// Which worksheets of a workbook are processed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SheetSelection {
+  /// the first worksheet only
+  #[default]
+  First,
+  All,
+  /// the named worksheets, in the given order
+  Names(Vec<String>),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SheetInfo {
+  pub name: String,
+  /// rows below the header
+  pub rows: usize,
+}
+
+/// A data row with its position in the sheet
+#[derive(Debug, Clone, Copy)]
+pub struct SheetRow<'a> {
+  /// 1 based, as shown by the spreadsheet app
+  pub number: usize,
+  pub cells: &'a [DataType],
+}
+
+/// The tables of a run and every problem found on the way
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessOutput {
+  pub item_meta: Vec<ItemMeta>,
+  pub report: ValidationReport,
+  /// measurement names missing from the glossary, left in Japanese unless they were
+  /// machine translated
+  pub untranslated: Vec<UntranslatedTerm>,
+}
+
+/// Parse the rows of one sheet, grouped by item code
+///
+/// Bad cells do not stop the run: they are added to `report` and the item they belong
+/// to is left out. `first_column` is the sheet column of the first cell of each row.
+pub fn process_excel_rows(
+  sheet_name: &str,
+  first_column: usize,
+  rows: Vec<SheetRow>,
+  indices: &ColumnIndices,
+  report: &mut ValidationReport,
+) -> Vec<Vec<ItemInfo>> {
+  // rows are already sorted by item code, so consecutive rows share one table
+  let item_code_isolated_rows = rows
+    .into_iter()
+    .group_by(|row| row.cells[indices.item_code].to_string());
+
+  let mut item_code_size_data = Vec::new();
+  for (_, item_code_isolated_row) in &item_code_isolated_rows {
+    let mut item_infos = Vec::new();
+    let mut has_issue = false;
+    for row in item_code_isolated_row {
+      let cell = |column: Column| row.cells[indices.get(column)].to_string();
+      let item_code = cell(Column::ItemCode)
+        .replace(" ", "_")
+        .parse::<ItemCode>()
+        .map_err(|e| Error::MelroseType(melrose_types::error::Error::from(e)));
+      let size_code = cell(Column::SizeCode)
+        .parse::<SizeCode>()
+        .map_err(|e| Error::MelroseType(melrose_types::error::Error::from(e)));
+      let size_text = cell(Column::SizeText).parse::<SizeDetails>();
+      match (item_code, size_code, size_text) {
+        (Ok(item_code), Ok(size_code), Ok(size_text)) => item_infos.push(ItemInfo {
+          item_code,
+          size_code,
+          size_text,
+        }),
+        (item_code, size_code, size_text) => {
+          has_issue = true;
+          let errors = [
+            (Column::ItemCode, item_code.err()),
+            (Column::SizeCode, size_code.err()),
+            (Column::SizeText, size_text.err()),
+          ];
+          for (column, error) in errors {
+            if let Some(error) = error {
+              report.push(RowIssue::new(
+                sheet_name,
+                row.number,
+                column,
+                first_column + indices.get(column),
+                cell(column),
+                error,
+              ));
+            }
+          }
+        }
+      }
+    }
+    if !has_issue {
+      item_code_size_data.push(item_infos);
+    }
+  }
+
+  item_code_size_data
+}
+
+/// The measurement names of a run translated into one language
+struct LanguageTranslation {
+  language: TargetLanguage,
+  /// every name, to its translation or itself
+  translations: HashMap<String, String>,
+  /// names the glossary has no entry for
+  missing: Vec<String>,
+  machine_translations: HashMap<String, String>,
+  /// translations of the names [`is_length`] holds for, told apart before translating
+  length_names: HashSet<String>,
+}
+
+/// Build the table of one item, its rows already in size order and its names in `language`
+///
+/// The header is the union of the measurement names of every size, and each value goes
+/// under its own name, so sizes listing measurements in another order still line up.
+/// A size without some measurement gets [`MISSING_CELL`] there and is added to `report`.
+/// `length_names` are the names whose column is marked in [`ItemTable::lengths`].
+pub fn create_item_meta(
+  sheet: &str,
+  item_infos: Vec<ItemInfo>,
+  language: TargetLanguage,
+  length_names: &HashSet<String>,
+  report: &mut ValidationReport,
+) -> ItemMeta {
+  let names = item_infos
+    .iter()
+    .flat_map(|item_info| item_info.size_text.names())
+    .unique()
+    .collect_vec();
+  let mut table_head = names.clone();
+  table_head.insert(0, size_header(language).to_string());
+
+  let mut table_body = Vec::new();
+  let mut missing = Vec::new();
+  for item_info in &item_infos {
+    let mut size_row_raw = vec![item_info.size_code.to_roman_numeral()];
+    let mut missing_names = Vec::new();
+    for name in &names {
+      match item_info.size_text.get(name) {
+        // as written in the sheet, the parsed value is only used to convert and validate
+        Some(detail) => size_row_raw.push(detail.value.clone()),
+        None => {
+          size_row_raw.push(MISSING_CELL.to_string());
+          missing_names.push(name.clone());
+        }
+      }
+    }
+    if !missing_names.is_empty() {
+      missing.push(MissingMeasurements {
+        size_code: item_info.size_code.to_string(),
+        names: missing_names,
+      });
+    }
+    table_body.push(size_row_raw);
+  }
+
+  let item_code = item_infos[0].item_code.to_string();
+  if !missing.is_empty() {
+    report.measurement_mismatches.push(MeasurementMismatch {
+      sheet: sheet.to_string(),
+      item_code: item_code.clone(),
+      missing,
+    });
+  }
+  let mut lengths = vec![false];
+  lengths.extend(names.iter().map(|name| length_names.contains(name)));
+  let table = ItemTable {
+    head: table_head,
+    body: table_body,
+    lengths,
+  };
+  ItemMeta {
+    code: item_code,
+    size_code: item_infos[0].size_code.to_string(),
+    sheet: sheet.to_string(),
+    language,
+    inch: false,
+    table,
+  }
+}
+
+/// open a .xlsx, .xlsm, .xlsb, .xls, .ods or CSV file
+pub fn read_excel_file(excel_path: impl AsRef<Path>) -> Result<InputWorkbook> {
+  InputWorkbook::open(excel_path)
+}
+
+pub fn get_sheet_data(
+  excel_file: &mut InputWorkbook,
+  index: usize,
+) -> Result<calamine::Range<DataType>> {
+  excel_file
+    .worksheet_range_at(index)
+    .ok_or(Error::EmptyFile)?
+    .map_err(|_| Error::EmptyFile)
+}
+
+/// every worksheet of the workbook with its number of data rows
+pub fn list_sheets(excel_path: impl AsRef<Path>) -> Result<Vec<SheetInfo>> {
+  let mut excel_file = read_excel_file(excel_path)?;
+  let names = excel_file.sheet_names();
+  let mut sheets = Vec::new();
+  for (index, name) in names.into_iter().enumerate() {
+    let rows = get_sheet_data(&mut excel_file, index)
+      .map(|sheet| sheet.height().saturating_sub(1))
+      .unwrap_or(0);
+    sheets.push(SheetInfo { name, rows });
+  }
+  Ok(sheets)
+}
+
+/// (index, name) of the worksheets to process
+pub fn select_sheets(
+  excel_file: &InputWorkbook,
+  selection: &SheetSelection,
+) -> Result<Vec<(usize, String)>> {
+  let names = excel_file.sheet_names();
+  match selection {
+    SheetSelection::First => Ok(names.into_iter().enumerate().take(1).collect()),
+    SheetSelection::All => Ok(names.into_iter().enumerate().collect()),
+    SheetSelection::Names(selected) => selected
+      .iter()
+      .map(|name| {
+        names
+          .iter()
+          .position(|sheet_name| sheet_name == name)
+          .map(|index| (index, name.clone()))
+          .ok_or_else(|| Error::SheetNotFound(name.clone()))
+      })
+      .collect(),
+  }
+}
+
+pub fn get_column_indices(
+  sheet: &calamine::Range<DataType>,
+  aliases: &ColumnAliases,
+) -> Result<ColumnIndices> {
+  let header = sheet.rows().next().ok_or(Error::EmptyFile)?;
+  let first_column = sheet.start().map_or(0, |(_, col)| col as usize);
+  ColumnIndices::detect(header.iter().map(|cell| cell.to_string()), aliases).map_err(|error| {
+    match error {
+      // the range starts at the first non-empty column, the message names sheet columns
+      Error::DuplicateColumn { column, headers } => Error::DuplicateColumn {
+        column,
+        headers: headers
+          .into_iter()
+          .map(|(i, header)| (first_column + i, header))
+          .collect(),
+      },
+      error => error,
+    }
+  })
+}
+
+/// The data rows with one row per (品番, SZ) pair, sorted by item code
+///
+/// Rows sharing a pair are added to `report`; `policy` picks the row kept when their
+/// [採寸] differ, or fails with every conflict of the sheet for [`DuplicatePolicy::Fail`].
+pub fn get_unique_rows<'a>(
+  sheet_name: &str,
+  sheet: &'a calamine::Range<DataType>,
+  indices: &ColumnIndices,
+  policy: DuplicatePolicy,
+  report: &mut ValidationReport,
+) -> Result<Vec<SheetRow<'a>>> {
+  // TODO)) 必要なフィールドに空欄がある場合、無視にする？
+  // いらない行に消し忘れがあると、気づかない
+  let first_row = sheet.start().map_or(0, |(row, _)| row as usize);
+  let cell = |row: &SheetRow, column: Column| row.cells[indices.get(column)].to_string();
+  let groups = sheet
+    .rows()
+    .enumerate()
+    .skip(1)
+    .map(|(i, cells)| SheetRow {
+      number: first_row + i + 1,
+      cells,
+    })
+    .into_group_map_by(|row| (cell(row, Column::ItemCode), cell(row, Column::SizeCode)));
+
+  let mut unique_rows = Vec::new();
+  let mut duplicates = Vec::new();
+  for ((item_code, size_code), rows) in groups {
+    if rows.len() == 1 {
+      unique_rows.extend(rows);
+      continue;
+    }
+    let values = rows
+      .iter()
+      .map(|row| cell(row, Column::SizeText))
+      .collect_vec();
+    let conflicting = !values.iter().map(|value| value.trim()).all_equal();
+    let kept = match (conflicting, policy) {
+      (true, DuplicatePolicy::LastWins) => rows[rows.len() - 1],
+      _ => rows[0],
+    };
+    duplicates.push(DuplicateRows {
+      sheet: sheet_name.to_string(),
+      item_code,
+      size_code,
+      rows: rows.iter().map(|row| row.number).collect(),
+      values,
+      conflicting,
+      kept_row: kept.number,
+    });
+    unique_rows.push(kept);
+  }
+  duplicates.sort_by_key(|duplicate| duplicate.rows[0]);
+  if policy == DuplicatePolicy::Fail && duplicates.iter().any(|duplicate| duplicate.conflicting) {
+    return Err(Error::ConflictingDuplicates(
+      duplicates
+        .into_iter()
+        .filter(|duplicate| duplicate.conflicting)
+        .collect(),
+    ));
+  }
+  report.duplicates.extend(duplicates);
+
+  // sheet order within an item, the sizes are sorted once the rows are parsed
+  unique_rows.sort_by(|a, b| {
+    cell(a, Column::ItemCode)
+      .cmp(&cell(b, Column::ItemCode))
+      .then(a.number.cmp(&b.number))
+  });
+  Ok(unique_rows)
+}
+
+/// Run the whole pipeline on a workbook: column detection, grouping by item code,
+/// `SizeDetails` parsing, measurement name normalization and glossary translation, with
+/// machine translation of the glossary misses when `options` asks for it.
+///
+/// Every selected worksheet is processed on its own, and each [`ItemMeta`] records
+/// the worksheet it came from. Bad cells end up in the [`ValidationReport`] instead of
+/// failing the run.
+///
+/// `on_state` is called whenever the pipeline moves on to another stage, so callers
+/// can forward the progress to the UI or a terminal.
+pub async fn process_workbook(
+  excel_path: impl AsRef<Path>,
+  translate_client: &mut TranslateClient,
+  options: &ProcessOptions,
+  mut on_state: impl FnMut(&str),
+) -> Result<ProcessOutput> {
+  on_state("processing file");
+  let mut excel_file = read_excel_file(excel_path)?;
+  let mut report = ValidationReport::default();
+  let mut sheet_data = Vec::new();
+  for (index, name) in select_sheets(&excel_file, &options.sheets)? {
+    let in_sheet = |error| Error::InSheet {
+      sheet: name.clone(),
+      error: Box::new(error),
+    };
+    let sheet = get_sheet_data(&mut excel_file, index).map_err(in_sheet)?;
+    // sheets without any data, such as notes or an empty template, are skipped
+    if sheet.height() < 2 {
+      continue;
+    }
+    let indices = get_column_indices(&sheet, &options.column_aliases).map_err(in_sheet)?;
+    let rows = get_unique_rows(
+      &name,
+      &sheet,
+      &indices,
+      options.duplicate_policy,
+      &mut report,
+    )
+    .map_err(in_sheet)?;
+    if rows.is_empty() {
+      continue;
+    }
+    let first_column = sheet.start().map_or(0, |(_, col)| col as usize);
+    let item_code_size_data = process_excel_rows(&name, first_column, rows, &indices, &mut report);
+    sheet_data.push((name, item_code_size_data));
+  }
+  if sheet_data.is_empty() {
+    return Err(Error::EmptyFile);
+  }
+
+  on_state("translating");
+  for item_info in sheet_data
+    .iter_mut()
+    .flat_map(|(_, item_code_size_data)| item_code_size_data.iter_mut().flatten())
+  {
+    item_info
+      .size_text
+      .normalize_names(&options.measurement_synonyms);
+  }
+  let names = sheet_data
+    .iter()
+    .flat_map(|(_, item_code_size_data)| item_code_size_data.iter().flatten())
+    .flat_map(|item_info| item_info.size_text.names())
+    .unique()
+    .collect_vec();
+  let mut languages = Vec::new();
+  for language in options.languages() {
+    let local = translate_client.translate_local(&names, language)?;
+    let mut translations: HashMap<String, String> =
+      names.iter().cloned().zip(local.translated).collect();
+    // glossary misses go to the translation api in one request per language
+    let mut machine_translations = HashMap::new();
+    if options.machine_translation && !local.missing.is_empty() {
+      on_state("machine translating");
+      let translated = translate_client.translate(&local.missing, language).await?;
+      // a backend that cannot translate a name hands it back unchanged
+      machine_translations = local
+        .missing
+        .iter()
+        .cloned()
+        .zip(translated)
+        .filter(|(name, translation)| name != translation)
+        .collect();
+      translations.extend(machine_translations.clone());
+    }
+    let length_names = translations
+      .iter()
+      .filter(|(name, _)| is_length(name))
+      .map(|(_, translation)| translation.clone())
+      .collect();
+    languages.push(LanguageTranslation {
+      language,
+      length_names,
+      translations,
+      missing: local.missing,
+      machine_translations,
+    });
+  }
+
+  on_state("processing file");
+  let mut item_meta = Vec::new();
+  let mut misses = vec![Vec::new(); languages.len()];
+  for (sheet, item_code_size_data) in &sheet_data {
+    for item_infos in item_code_size_data {
+      // the tables of one item follow each other, in the order of the languages
+      for (i, translation) in languages.iter().enumerate() {
+        let mut item_infos = item_infos.clone();
+        for item_info in &mut item_infos {
+          let item_code = item_info.item_code.to_string();
+          misses[i].extend(
+            item_info
+              .size_text
+              .names()
+              .into_iter()
+              .filter(|name| translation.missing.contains(name))
+              .map(|name| (name, item_code.clone())),
+          );
+          item_info.size_text.rename(&translation.translations);
+        }
+        options.size_order.sort(&mut item_infos);
+        // the same cells are missing in every language, so they are reported once
+        let mut other_report = ValidationReport::default();
+        let report = if i == 0 {
+          &mut report
+        } else {
+          &mut other_report
+        };
+        item_meta.push(create_item_meta(
+          sheet,
+          item_infos,
+          translation.language,
+          &translation.length_names,
+          report,
+        ));
+      }
+    }
+  }
+  let untranslated = languages
+    .iter()
+    .zip(misses)
+    .flat_map(|(translation, misses)| {
+      untranslated_terms(
+        misses,
+        &translation.machine_translations,
+        translation.language,
+      )
+    })
+    .collect();
+  Ok(ProcessOutput {
+    item_meta,
+    report,
+    untranslated,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use async_trait::async_trait;
+  use phdb_translate::Translator;
+
+  use super::*;
+  use crate::validation::IssueKind;
+
+  /// translates the names it has an entry for, and hands back the others like a backend
+  /// that cannot translate them
+  struct StubTranslator(HashMap<String, String>);
+
+  #[async_trait]
+  impl Translator for StubTranslator {
+    fn name(&self) -> &'static str {
+      "stub"
+    }
+
+    fn is_remote(&self) -> bool {
+      false
+    }
+
+    async fn translate(
+      &mut self,
+      inputs: &[String],
+      _: TargetLanguage,
+    ) -> std::result::Result<Vec<String>, phdb_translate::Error> {
+      Ok(
+        inputs
+          .iter()
+          .map(|input| self.0.get(input).unwrap_or(input).clone())
+          .collect(),
+      )
+    }
+  }
+
+  fn sheet(rows: &[[&str; 3]]) -> calamine::Range<DataType> {
+    let mut range = calamine::Range::new((0, 0), (rows.len() as u32 - 1, 2));
+    for (i, row) in rows.iter().enumerate() {
+      for (j, cell) in row.iter().enumerate() {
+        range.set_value((i as u32, j as u32), DataType::String(cell.to_string()));
+      }
+    }
+    range
+  }
+
+  #[test]
+  fn table_of_every_measurement() {
+    let item_infos = ["01", "02", "03"]
+      .into_iter()
+      .zip(["肩幅:40 着丈:60", "着丈:62 肩幅:42 袖丈:20", "肩幅:44"])
+      .map(|(size_code, size_text)| ItemInfo {
+        item_code: "A-1".parse().unwrap(),
+        size_code: size_code.parse().unwrap(),
+        size_text: size_text.parse().unwrap(),
+      })
+      .collect_vec();
+    let sizes = item_infos
+      .iter()
+      .map(|item_info| item_info.size_code.to_roman_numeral())
+      .collect_vec();
+    let size_codes = item_infos
+      .iter()
+      .map(|item_info| item_info.size_code.to_string())
+      .collect_vec();
+    let mut report = ValidationReport::default();
+    let meta = create_item_meta(
+      "Sheet1",
+      item_infos,
+      TargetLanguage::ZhHans,
+      &HashSet::new(),
+      &mut report,
+    );
+
+    assert_eq!(meta.table.head, ["尺码", "肩幅", "着丈", "袖丈"]);
+    assert_eq!(
+      meta.table.body,
+      [
+        [sizes[0].as_str(), "40", "60", MISSING_CELL],
+        [sizes[1].as_str(), "42", "62", "20"],
+        [sizes[2].as_str(), "44", MISSING_CELL, MISSING_CELL],
+      ]
+    );
+    let mismatch = &report.measurement_mismatches[0];
+    assert_eq!(report.measurement_mismatches.len(), 1);
+    assert_eq!(
+      (mismatch.sheet.as_str(), mismatch.item_code.as_str()),
+      ("Sheet1", "A-1")
+    );
+    let missing = mismatch
+      .missing
+      .iter()
+      .map(|missing| (missing.size_code.as_str(), missing.names.clone()))
+      .collect_vec();
+    assert_eq!(
+      missing,
+      [
+        (size_codes[0].as_str(), vec!["袖丈".to_string()]),
+        (
+          size_codes[2].as_str(),
+          vec!["着丈".to_string(), "袖丈".to_string()]
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn duplicate_column_in_sheet_columns() {
+    // column A is empty, so the range starts at column B
+    let mut sheet = calamine::Range::new((0, 1), (0, 4));
+    for (i, header) in ["品番", "SZ", "採寸", "品番"].into_iter().enumerate() {
+      sheet.set_value((0, i as u32 + 1), DataType::String(header.to_string()));
+    }
+    let Err(Error::DuplicateColumn { column, headers }) =
+      get_column_indices(&sheet, &ColumnAliases::default())
+    else {
+      panic!("two [品番] headers should fail");
+    };
+    assert_eq!(column, Column::ItemCode);
+    assert_eq!(headers, [(1, "品番".to_string()), (4, "品番".to_string())]);
+  }
+
+  #[test]
+  fn bad_cells_leave_out_their_item() {
+    // column A is empty, so the range starts at column B
+    let rows = [
+      ["品番", "SZ", "採寸"],
+      ["A-1", "01", "肩幅:40"],
+      ["B-2", "01", "肩幅40"],
+      ["B-2", "02", ""],
+      ["C-3", "01", "肩幅:42"],
+    ];
+    let mut sheet = calamine::Range::new((0, 1), (rows.len() as u32 - 1, 3));
+    for (i, row) in rows.iter().enumerate() {
+      for (j, cell) in row.iter().enumerate().filter(|(_, cell)| !cell.is_empty()) {
+        sheet.set_value((i as u32, j as u32 + 1), DataType::String(cell.to_string()));
+      }
+    }
+    let indices = get_column_indices(&sheet, &ColumnAliases::default()).unwrap();
+    let mut report = ValidationReport::default();
+    let unique_rows = get_unique_rows(
+      "Sheet1",
+      &sheet,
+      &indices,
+      DuplicatePolicy::FirstWins,
+      &mut report,
+    )
+    .unwrap();
+    let items = process_excel_rows("Sheet1", 1, unique_rows, &indices, &mut report);
+
+    let codes = items
+      .iter()
+      .map(|item_infos| item_infos[0].item_code.to_string())
+      .collect_vec();
+    assert_eq!(codes, ["A-1", "C-3"]);
+    let issues = report
+      .issues
+      .iter()
+      .map(|issue| {
+        (
+          issue.sheet.as_str(),
+          issue.row,
+          issue.column_name.as_str(),
+          issue.value.as_str(),
+          issue.kind,
+        )
+      })
+      .collect_vec();
+    assert_eq!(
+      issues,
+      [
+        ("Sheet1", 3, "D", "肩幅40", IssueKind::InvalidSizeText),
+        ("Sheet1", 4, "D", "", IssueKind::EmptySizeText),
+      ]
+    );
+  }
+
+  #[test]
+  fn resolve_duplicate_rows() {
+    let sheet = sheet(&[
+      ["品番", "SZ", "採寸"],
+      ["B-2", "01", "肩幅:40"],
+      ["A-1", "01", "肩幅:40"],
+      ["B-2", "01", "肩幅:41"],
+      ["A-1", "01", "肩幅:40 "],
+      ["A-1", "02", "肩幅:42"],
+    ]);
+    let indices = ColumnIndices {
+      item_code: 0,
+      size_code: 1,
+      size_text: 2,
+    };
+    let unique_rows = |policy| {
+      let mut report = ValidationReport::default();
+      let rows = get_unique_rows("Sheet1", &sheet, &indices, policy, &mut report).unwrap();
+      (rows.iter().map(|row| row.number).collect_vec(), report)
+    };
+
+    let (rows, report) = unique_rows(DuplicatePolicy::FirstWins);
+    assert_eq!(rows, [3, 6, 2]);
+    assert_eq!(report.duplicates.len(), 2);
+    assert_eq!(report.duplicates[0].rows, [2, 4]);
+    assert_eq!(report.duplicates[0].values, ["肩幅:40", "肩幅:41"]);
+    assert!(!report.duplicates[1].conflicting);
+    assert_eq!(report.conflicts().count(), 1);
+
+    let (rows, _) = unique_rows(DuplicatePolicy::LastWins);
+    assert_eq!(rows, [3, 6, 4]);
+
+    let mut report = ValidationReport::default();
+    let result = get_unique_rows(
+      "Sheet1",
+      &sheet,
+      &indices,
+      DuplicatePolicy::Fail,
+      &mut report,
+    );
+    let Err(Error::ConflictingDuplicates(conflicts)) = result else {
+      panic!("conflicting rows should fail the run");
+    };
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].item_code, "B-2");
+    assert_eq!(conflicts[0].rows, [2, 4]);
+    assert_eq!(conflicts[0].values, ["肩幅:40", "肩幅:41"]);
+  }
+
+  #[tokio::test]
+  async fn machine_translated_glossary_misses() {
+    let path = std::env::temp_dir().join(format!("stg-machine-{}.csv", std::process::id()));
+    std::fs::write(&path, "品番,SZ,採寸\nA-1,01,肩幅:40 裄丈:70 ボタン数:5\n").unwrap();
+    let glossary = HashMap::from([("肩幅".to_string(), "肩宽".to_string())]);
+    let mut client = TranslateClient::with_glossary(glossary);
+    client.set_translator(Box::new(StubTranslator(HashMap::from([(
+      "裄丈".to_string(),
+      "袖长".to_string(),
+    )]))));
+    let options = ProcessOptions {
+      machine_translation: true,
+      ..Default::default()
+    };
+    let output = process_workbook(&path, &mut client, &options, |_| {}).await;
+    std::fs::remove_file(&path).unwrap();
+    let output = output.unwrap();
+
+    assert_eq!(
+      output.item_meta[0].table.head,
+      ["尺码", "肩宽", "袖长", "ボタン数"]
+    );
+    let term = |term: &str, machine_translation: Option<&str>| UntranslatedTerm {
+      term: term.to_string(),
+      language: TargetLanguage::ZhHans,
+      item_codes: vec!["A-1".to_string()],
+      machine_translation: machine_translation.map(String::from),
+    };
+    assert_eq!(
+      output.untranslated,
+      [term("ボタン数", None), term("裄丈", Some("袖长"))]
+    );
+  }
+}
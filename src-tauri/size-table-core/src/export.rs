@@ -0,0 +1,256 @@
+use std::{
+  collections::{HashMap, HashSet},
+  fs::{self, File},
+  io::BufWriter,
+  path::{Path, PathBuf},
+  str::FromStr,
+};
+
+use phdb_translate::TargetLanguage;
+
+use crate::{
+  html::{write_index_html, write_table_html},
+  labels::{document_title, table_title},
+  layout::TableStyle,
+  models::{ItemMeta, ItemTable},
+  pdf::{write_catalog_pdf, write_item_pdf},
+  render::{write_table_image, RasterFormat, RenderOptions},
+  svg::write_svg,
+  xlsx::{write_xlsx, XlsxLayout},
+  Error, Result,
+};
+
+/// Output formats supported when writing generated size tables to disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+  /// every table in a single `size_tables.json`
+  Json,
+  /// one `size_<code>.csv` per item code
+  Csv,
+  /// one `size_<code>.png` per item code
+  Png,
+  /// one `size_<code>.jpg` per item code, same as the images saved by the app
+  Jpeg,
+  /// one `size_<code>.svg` per item code, with editable text
+  Svg,
+  /// one single page `size_<code>.pdf` per item code
+  Pdf,
+  /// every table in a single `size_tables.pdf`, one page per item code
+  PdfCatalog,
+  /// one inline-styled `size_<code>.html` fragment per item code and an `index.html` preview
+  Html,
+  /// every table in `size_tables.xlsx`, one worksheet per item code
+  Xlsx,
+  /// every table in `size_tables.xlsx` on a single worksheet with a [品番] column
+  XlsxSingleSheet,
+}
+
+impl FromStr for ExportFormat {
+  type Err = Error;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    match s.trim().to_ascii_lowercase().as_str() {
+      "json" => Ok(Self::Json),
+      "csv" => Ok(Self::Csv),
+      "png" => Ok(Self::Png),
+      "jpg" | "jpeg" => Ok(Self::Jpeg),
+      "svg" => Ok(Self::Svg),
+      "pdf" => Ok(Self::Pdf),
+      "pdf-catalog" => Ok(Self::PdfCatalog),
+      "html" => Ok(Self::Html),
+      "xlsx" => Ok(Self::Xlsx),
+      "xlsx-single" => Ok(Self::XlsxSingleSheet),
+      other => Err(Error::UnsupportedExportFormat(other.to_string())),
+    }
+  }
+}
+
+/// Write the tables to `out_dir` and return the paths of every written file.
+///
+/// Per-item files follow the same layout as the images saved by the app:
+/// `<out_dir>/<code>/size_<code>.<ext>`, see [`ItemFileNames`]
+pub fn export_item_metas(
+  item_meta: &[ItemMeta],
+  out_dir: impl AsRef<Path>,
+  format: ExportFormat,
+) -> Result<Vec<PathBuf>> {
+  let out_dir = out_dir.as_ref();
+  let names = ItemFileNames::new(item_meta);
+  fs::create_dir_all(out_dir).map_err(Error::FileWrite)?;
+  match format {
+    ExportFormat::Json => {
+      let path = out_dir.join("size_tables.json");
+      write_json(item_meta, &path)?;
+      Ok(vec![path])
+    }
+    ExportFormat::Csv => item_meta
+      .iter()
+      .map(|meta| {
+        let path = item_file_path(out_dir, &names, meta, "csv")?;
+        write_csv(&meta.table, &path)?;
+        Ok(path)
+      })
+      .collect(),
+    ExportFormat::Png => write_images(item_meta, out_dir, &names, RasterFormat::Png),
+    ExportFormat::Jpeg => write_images(item_meta, out_dir, &names, RasterFormat::Jpeg),
+    ExportFormat::Svg => {
+      let style = TableStyle::default();
+      item_meta
+        .iter()
+        .map(|meta| {
+          let path = item_file_path(out_dir, &names, meta, "svg")?;
+          write_svg(&meta.table, table_title(meta.language), &path, &style)?;
+          Ok(path)
+        })
+        .collect()
+    }
+    ExportFormat::Pdf => {
+      let style = TableStyle::default();
+      item_meta
+        .iter()
+        .map(|meta| {
+          let path = item_file_path(out_dir, &names, meta, "pdf")?;
+          write_item_pdf(meta, &path, &style)?;
+          Ok(path)
+        })
+        .collect()
+    }
+    ExportFormat::PdfCatalog => {
+      let path = out_dir.join("size_tables.pdf");
+      let title = document_title(item_meta);
+      write_catalog_pdf(item_meta, &title, &path, &TableStyle::default())?;
+      Ok(vec![path])
+    }
+    ExportFormat::Html => {
+      let style = TableStyle::default();
+      let mut written = item_meta
+        .iter()
+        .map(|meta| {
+          let path = item_file_path(out_dir, &names, meta, "html")?;
+          let caption = table_title(meta.language);
+          write_table_html(&meta.table, &path, Some(caption), &style)?;
+          Ok(path)
+        })
+        .collect::<Result<Vec<_>>>()?;
+      let index_path = out_dir.join("index.html");
+      write_index_html(item_meta, &index_path, true, &style)?;
+      written.push(index_path);
+      Ok(written)
+    }
+    ExportFormat::Xlsx | ExportFormat::XlsxSingleSheet => {
+      let layout = match format {
+        ExportFormat::XlsxSingleSheet => XlsxLayout::SingleSheet,
+        _ => XlsxLayout::SheetPerItem,
+      };
+      let path = out_dir.join("size_tables.xlsx");
+      write_xlsx(item_meta, &path, layout)?;
+      Ok(vec![path])
+    }
+  }
+}
+
+pub fn write_json(item_meta: &[ItemMeta], path: impl AsRef<Path>) -> Result<()> {
+  let file = File::create(path).map_err(Error::FileWrite)?;
+  serde_json::to_writer_pretty(BufWriter::new(file), item_meta)
+    .map_err(|e| Error::FileWrite(e.into()))?;
+  Ok(())
+}
+
+pub fn write_csv(table: &ItemTable, path: impl AsRef<Path>) -> Result<()> {
+  let mut writer = csv::Writer::from_path(path)?;
+  writer.write_record(&table.head)?;
+  for row in &table.body {
+    writer.write_record(row)?;
+  }
+  writer.flush()?;
+  Ok(())
+}
+
+fn write_images(
+  item_meta: &[ItemMeta],
+  out_dir: &Path,
+  names: &ItemFileNames,
+  format: RasterFormat,
+) -> Result<Vec<PathBuf>> {
+  let options = RenderOptions::default();
+  item_meta
+    .iter()
+    .map(|meta| {
+      let path = item_file_path(out_dir, names, meta, format.extension())?;
+      let title = table_title(meta.language);
+      write_table_image(&meta.table, title, &path, format, &options)?;
+      Ok(path)
+    })
+    .collect()
+}
+
+/// Names of the per-item files
+///
+/// An item code found on several worksheets gets the sheet name appended, such as
+/// `size_A-1_Sheet2`, tables in another language than Simplified Chinese get the
+/// language code, such as `size_A-1_ko`, and inch tables end in `_inch`, so no table
+/// overwrites another.
+pub struct ItemFileNames<'a> {
+  shared_codes: HashSet<&'a str>,
+}
+
+impl<'a> ItemFileNames<'a> {
+  pub fn new(item_meta: &'a [ItemMeta]) -> Self {
+    let mut sheets: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for meta in item_meta {
+      sheets.entry(&meta.code).or_default().insert(&meta.sheet);
+    }
+    let shared_codes = sheets
+      .into_iter()
+      .filter(|(_, sheets)| sheets.len() > 1)
+      .map(|(code, _)| code)
+      .collect();
+    Self { shared_codes }
+  }
+
+  /// the item code, followed by the sheet name when the code is on several sheets, by
+  /// the language code when it is not Simplified Chinese and by `inch` for inch tables
+  pub fn name(&self, meta: &ItemMeta) -> String {
+    let mut name = meta.code.clone();
+    if self.shared_codes.contains(meta.code.as_str()) {
+      name = format!("{name}_{}", file_name_safe(&meta.sheet));
+    }
+    if meta.language != TargetLanguage::default() {
+      name = format!("{name}_{}", meta.language);
+    }
+    if meta.inch {
+      name.push_str("_inch");
+    }
+    name
+  }
+
+  /// `<code>/size_<name>.<ext>`, relative to the output directory
+  pub fn relative_path(&self, meta: &ItemMeta, ext: &str) -> String {
+    format!("{}/size_{}.{ext}", meta.code, self.name(meta))
+  }
+}
+
+/// `text` with the characters windows and macOS reject in file names replaced
+fn file_name_safe(text: &str) -> String {
+  text
+    .chars()
+    .map(|c| match c {
+      '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+      c => c,
+    })
+    .collect()
+}
+
+/// `<out_dir>/<code>/size_<name>.<ext>`, creating the item directory when needed
+fn item_file_path(
+  out_dir: &Path,
+  names: &ItemFileNames,
+  meta: &ItemMeta,
+  ext: &str,
+) -> Result<PathBuf> {
+  let path = out_dir.join(names.relative_path(meta, ext));
+  if let Some(item_dir) = path.parent() {
+    fs::create_dir_all(item_dir).map_err(Error::FileWrite)?;
+  }
+  Ok(path)
+}
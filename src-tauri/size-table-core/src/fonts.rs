@@ -0,0 +1,60 @@
+use ab_glyph::FontRef;
+
+use crate::{Error, Result};
+
+// ビルド時に生成されたフォントモジュールをインクルード
+#[path = ""]
+mod embedded_font {
+  include!(concat!(env!("OUT_DIR"), "/font.rs"));
+}
+
+/// The font family the webview uses for the size table, kept for the vector outputs
+pub const FONT_FAMILY: &str = "Noto Sans SC";
+
+/// raw bytes of the CJK font embedded at build time
+pub fn font_data() -> Result<&'static [u8]> {
+  let data = embedded_font::get_font_data();
+  if data.is_empty() {
+    return Err(Error::FontNotEmbedded);
+  }
+  Ok(data)
+}
+
+pub fn embedded_font() -> Result<FontRef<'static>> {
+  FontRef::try_from_slice(font_data()?).map_err(|_| Error::FontNotEmbedded)
+}
+
+#[cfg(test)]
+mod tests {
+  use ab_glyph::Font;
+  use phdb_translate::TargetLanguage;
+
+  use super::*;
+  use crate::labels::{item_code_label, size_header, table_title};
+
+  #[test]
+  fn glyphs_of_every_language() {
+    let font = embedded_font().unwrap();
+    for language in TargetLanguage::ALL {
+      // measurement names as the glossaries translate them
+      let sample = match language {
+        TargetLanguage::ZhHans => "肩宽衣长袖长",
+        TargetLanguage::ZhHant => "肩寬衣長袖長",
+        TargetLanguage::Ko => "어깨너비 총장 소매길이",
+        TargetLanguage::En => "Shoulder Length Sleeve",
+      };
+      let texts = [
+        size_header(language),
+        table_title(language),
+        item_code_label(language),
+        sample,
+      ];
+      let missing = texts
+        .concat()
+        .chars()
+        .filter(|c| !c.is_whitespace() && font.glyph_id(*c).0 == 0)
+        .collect::<String>();
+      assert!(missing.is_empty(), "no glyph for {missing} ({language})");
+    }
+  }
+}
@@ -0,0 +1,191 @@
+use std::{fmt::Write as _, fs, path::Path};
+
+use crate::{
+  export::ItemFileNames,
+  fonts::FONT_FAMILY,
+  labels::{document_title, html_lang, table_title},
+  layout::TableStyle,
+  models::{ItemMeta, ItemTable},
+  svg::{escape_xml, num},
+  Error, Result,
+};
+
+/// Build a self-contained `<table>` fragment that can be pasted into a product detail page
+///
+/// Every style is inlined, because the storefront editors drop `<style>` and `class`
+pub fn render_table_html(table: &ItemTable, caption: Option<&str>, style: &TableStyle) -> String {
+  let table_style = format!(
+    "border-collapse:collapse;border:{}px solid {};font-family:'{}',sans-serif;font-size:{}px;color:{};background:{};",
+    num(style.border_width),
+    style.border_color.to_hex(),
+    FONT_FAMILY,
+    num(style.font_size),
+    style.text_color.to_hex(),
+    style.background.to_hex(),
+  );
+  let caption_style = format!(
+    "font-size:{}px;padding-bottom:{}px;",
+    num(style.title_font_size),
+    num(style.title_gap),
+  );
+  let cell_style = format!(
+    "min-width:{}px;padding:{}px;text-align:center;font-weight:normal;",
+    num(style.min_cell_width),
+    num(style.cell_padding),
+  );
+  let head_style = format!(
+    "{cell_style}color:{};border-bottom:{}px solid {};",
+    style.head_color.to_hex(),
+    num(style.border_width),
+    style.border_color.to_hex(),
+  );
+
+  let mut html = String::new();
+  // String への書き込みは失敗しない
+  let _ = writeln!(html, r#"<table style="{table_style}">"#);
+  if let Some(caption) = caption {
+    let _ = writeln!(
+      html,
+      r#"  <caption style="{caption_style}">{}</caption>"#,
+      escape_xml(caption)
+    );
+  }
+  html.push_str("  <thead>\n    <tr>\n");
+  for head in &table.head {
+    let _ = writeln!(
+      html,
+      r#"      <th scope="col" style="{head_style}">{}</th>"#,
+      escape_xml(head)
+    );
+  }
+  html.push_str("    </tr>\n  </thead>\n  <tbody>\n");
+  for row in &table.body {
+    html.push_str("    <tr>\n");
+    for (i, cell) in row.iter().enumerate() {
+      // the first column is the size label, so it heads its row
+      let _ = if i == 0 {
+        writeln!(
+          html,
+          r#"      <th scope="row" style="{cell_style}">{}</th>"#,
+          escape_xml(cell)
+        )
+      } else {
+        writeln!(
+          html,
+          r#"      <td style="{cell_style}">{}</td>"#,
+          escape_xml(cell)
+        )
+      };
+    }
+    html.push_str("    </tr>\n");
+  }
+  html.push_str("  </tbody>\n</table>\n");
+  html
+}
+
+/// A page previewing every fragment, with links to the per item files
+///
+/// With `with_caption`, every table gets the title of its own language.
+pub fn render_index_html(item_meta: &[ItemMeta], with_caption: bool, style: &TableStyle) -> String {
+  let lang = html_lang(
+    item_meta
+      .first()
+      .map(|meta| meta.language)
+      .unwrap_or_default(),
+  );
+  let mut html = format!(
+    "<!DOCTYPE html>\n<html lang=\"{lang}\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n",
+    escape_xml(&document_title(item_meta)),
+  );
+  let names = ItemFileNames::new(item_meta);
+  for meta in item_meta {
+    let code = escape_xml(&meta.code);
+    let href = escape_xml(&names.relative_path(meta, "html"));
+    let caption = with_caption.then(|| table_title(meta.language));
+    let _ = writeln!(
+      html,
+      r#"<section lang="{}" style="margin-bottom:32px;">"#,
+      html_lang(meta.language)
+    );
+    let _ = writeln!(
+      html,
+      r#"<h2 style="font-size:16px;"><a href="{href}">{code}</a></h2>"#
+    );
+    html.push_str(&render_table_html(&meta.table, caption, style));
+    html.push_str("</section>\n");
+  }
+  html.push_str("</body>\n</html>\n");
+  html
+}
+
+pub fn write_table_html(
+  table: &ItemTable,
+  path: impl AsRef<Path>,
+  caption: Option<&str>,
+  style: &TableStyle,
+) -> Result<()> {
+  fs::write(path, render_table_html(table, caption, style)).map_err(Error::FileWrite)?;
+  Ok(())
+}
+
+pub fn write_index_html(
+  item_meta: &[ItemMeta],
+  path: impl AsRef<Path>,
+  with_caption: bool,
+  style: &TableStyle,
+) -> Result<()> {
+  fs::write(path, render_index_html(item_meta, with_caption, style)).map_err(Error::FileWrite)?;
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use phdb_translate::TargetLanguage;
+
+  use super::*;
+
+  #[test]
+  fn table_fragment() {
+    let table = ItemTable {
+      head: vec!["尺码".to_string(), "肩宽".to_string()],
+      body: vec![vec!["S".to_string(), "42<5".to_string()]],
+      lengths: Vec::new(),
+    };
+    let html = render_table_html(&table, Some("尺码表"), &TableStyle::default());
+    assert!(html.starts_with("<table style=\""));
+    assert!(html.contains(">尺码表</caption>"));
+    assert!(html.contains(r#"<th scope="col""#));
+    assert!(html.contains(r#"<th scope="row" style="min-width:48px;padding:4.8px;"#));
+    assert!(html.contains(">42&lt;5</td>"));
+    assert!(!html.contains("class="));
+
+    let without_caption = render_table_html(&table, None, &TableStyle::default());
+    assert!(!without_caption.contains("<caption"));
+  }
+
+  #[test]
+  fn index_captions_follow_languages() {
+    let meta = |code: &str, language, head: &str| ItemMeta {
+      code: code.to_string(),
+      size_code: "01".to_string(),
+      sheet: "Sheet1".to_string(),
+      language,
+      inch: false,
+      table: ItemTable {
+        head: vec![head.to_string()],
+        body: vec![vec!["S".to_string()]],
+        lengths: Vec::new(),
+      },
+    };
+    let item_meta = [
+      meta("A-1", TargetLanguage::ZhHans, "尺码"),
+      meta("A-1", TargetLanguage::Ko, "사이즈"),
+    ];
+    let html = render_index_html(&item_meta, true, &TableStyle::default());
+    assert!(html.contains("<html lang=\"zh-CN\">"));
+    assert!(html.contains("<title>尺码表 / 사이즈표</title>"));
+    assert!(html.contains(r#"<section lang="ko""#));
+    assert!(html.contains(">사이즈표</caption>"));
+    assert!(html.contains(r#"href="A-1/size_A-1_ko.html""#));
+  }
+}
@@ -0,0 +1,247 @@
+use std::{
+  borrow::Cow,
+  fs::{self, File},
+  io::{BufReader, Read},
+  path::Path,
+};
+
+use calamine::{DataType, Ods, Range, Reader, Sheets, Xls, Xlsb, Xlsx};
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+
+use crate::{Error, Result};
+
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+/// compound file binary, the container of legacy .xls files
+const CFB_MAGIC: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+/// stored uncompressed right after the first zip entry header of every .ods file
+const ODS_MIMETYPE: &[u8] = b"application/vnd.oasis.opendocument.spreadsheet";
+
+/// A source file of any supported format, read through the same calls as a workbook
+///
+/// Spreadsheets (.xlsx, .xlsm, .xlsb, .xls, .ods) go through calamine; a CSV file is
+/// decoded up front and behaves like a workbook with a single sheet named after the file.
+pub enum InputWorkbook {
+  Spreadsheet(Sheets<BufReader<File>>),
+  Csv {
+    name: String,
+    range: Range<DataType>,
+  },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InputFormat {
+  Xlsx,
+  Xlsb,
+  Xls,
+  Ods,
+  Csv,
+}
+
+impl InputWorkbook {
+  /// open `path`, telling the format from the file content first and the extension second
+  pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+    let path = path.as_ref();
+    match detect_format(path)? {
+      InputFormat::Csv => Ok(Self::Csv {
+        name: path
+          .file_stem()
+          .map(|stem| stem.to_string_lossy().into_owned())
+          .unwrap_or_default(),
+        range: read_csv(path)?,
+      }),
+      format => {
+        let reader = BufReader::new(File::open(path)?);
+        let sheets = match format {
+          InputFormat::Xls => Xls::new(reader).map(Sheets::Xls).ok(),
+          InputFormat::Xlsb => Xlsb::new(reader).map(Sheets::Xlsb).ok(),
+          InputFormat::Ods => Ods::new(reader).map(Sheets::Ods).ok(),
+          _ => Xlsx::new(reader).map(Sheets::Xlsx).ok(),
+        };
+        sheets.map(Self::Spreadsheet).ok_or(Error::ExcelRead)
+      }
+    }
+  }
+
+  pub fn sheet_names(&self) -> Vec<String> {
+    match self {
+      Self::Spreadsheet(sheets) => sheets.sheet_names().to_vec(),
+      Self::Csv { name, .. } => vec![name.clone()],
+    }
+  }
+
+  pub fn worksheet_range_at(&mut self, index: usize) -> Option<Result<Range<DataType>>> {
+    match self {
+      Self::Spreadsheet(sheets) => sheets
+        .worksheet_range_at(index)
+        .map(|range| range.map_err(|_| Error::ExcelRead)),
+      Self::Csv { range, .. } => (index == 0).then(|| Ok(range.clone())),
+    }
+  }
+}
+
+fn detect_format(path: &Path) -> Result<InputFormat> {
+  let mut head = Vec::with_capacity(128);
+  File::open(path)?.take(128).read_to_end(&mut head)?;
+  let extension = path
+    .extension()
+    .map(|extension| extension.to_string_lossy().to_lowercase())
+    .unwrap_or_default();
+
+  if head.starts_with(CFB_MAGIC) {
+    return Ok(InputFormat::Xls);
+  }
+  if head.starts_with(ZIP_MAGIC) {
+    return Ok(
+      if head
+        .windows(ODS_MIMETYPE.len())
+        .any(|window| window == ODS_MIMETYPE)
+      {
+        InputFormat::Ods
+      } else if extension == "xlsb" {
+        InputFormat::Xlsb
+      } else {
+        InputFormat::Xlsx
+      },
+    );
+  }
+  match extension.as_str() {
+    "csv" | "tsv" | "txt" => Ok(InputFormat::Csv),
+    // not a zip nor a compound file, so it cannot be a workbook whatever it is named
+    "xlsx" | "xlsm" | "xlsb" | "xls" | "ods" => Err(Error::ExcelRead),
+    _ if !head.contains(&0) => Ok(InputFormat::Csv),
+    _ => Err(Error::UnsupportedInputFormat(extension)),
+  }
+}
+
+/// decode the whole file, which our PLM exports as Shift_JIS and newer tools as UTF-8
+pub fn decode_text(bytes: &[u8]) -> Cow<'_, str> {
+  let encoding = match Encoding::for_bom(bytes) {
+    Some((encoding, _)) => encoding,
+    None => {
+      let mut detector = EncodingDetector::new();
+      detector.feed(bytes, true);
+      detector.guess(Some(b"jp"), true)
+    }
+  };
+  // decode() strips the BOM itself
+  encoding.decode(bytes).0
+}
+
+fn read_csv(path: &Path) -> Result<Range<DataType>> {
+  let bytes = fs::read(path)?;
+  let text = decode_text(&bytes);
+  let first_line = text.lines().next().unwrap_or_default();
+  let delimiter = if first_line.contains('\t') && !first_line.contains(',') {
+    b'\t'
+  } else {
+    b','
+  };
+
+  let records = csv::ReaderBuilder::new()
+    .has_headers(false)
+    .flexible(true)
+    .delimiter(delimiter)
+    .from_reader(text.as_bytes())
+    .into_records()
+    .collect::<std::result::Result<Vec<_>, _>>()
+    .map_err(Error::CsvRead)?;
+
+  let height = records.len();
+  let width = records.iter().map(|record| record.len()).max().unwrap_or(0);
+  if height == 0 || width == 0 {
+    return Ok(Range::empty());
+  }
+  let mut range = Range::new((0, 0), (height as u32 - 1, width as u32 - 1));
+  for (row, record) in records.iter().enumerate() {
+    for (col, cell) in record.iter().enumerate() {
+      if !cell.is_empty() {
+        range.set_value((row as u32, col as u32), DataType::String(cell.to_string()));
+      }
+    }
+  }
+  Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+  use std::path::PathBuf;
+
+  use super::*;
+
+  fn temp_file(name: &str, content: &[u8]) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("stg-input-{}-{name}", std::process::id()));
+    fs::write(&path, content).unwrap();
+    path
+  }
+
+  fn format_of(name: &str, content: &[u8]) -> Result<InputFormat> {
+    let path = temp_file(name, content);
+    let format = detect_format(&path);
+    fs::remove_file(&path).unwrap();
+    format
+  }
+
+  #[test]
+  fn detect_format_from_content() {
+    let mut xls = CFB_MAGIC.to_vec();
+    xls.resize(512, 0);
+    let mut zip = ZIP_MAGIC.to_vec();
+    zip.resize(64, 0);
+    let mut ods = ZIP_MAGIC.to_vec();
+    ods.extend_from_slice(&[0; 26]);
+    ods.extend_from_slice(b"mimetype");
+    ods.extend_from_slice(ODS_MIMETYPE);
+
+    assert_eq!(format_of("a.xls", &xls).unwrap(), InputFormat::Xls);
+    assert_eq!(format_of("a.xlsx", &zip).unwrap(), InputFormat::Xlsx);
+    assert_eq!(format_of("a.xlsm", &zip).unwrap(), InputFormat::Xlsx);
+    assert_eq!(format_of("a.xlsb", &zip).unwrap(), InputFormat::Xlsb);
+    assert_eq!(format_of("a.ods", &ods).unwrap(), InputFormat::Ods);
+    // the content wins over a wrong extension
+    assert_eq!(format_of("b.xlsx", &xls).unwrap(), InputFormat::Xls);
+    assert_eq!(format_of("b.xlsx", &ods).unwrap(), InputFormat::Ods);
+    assert_eq!(format_of("b.csv", &zip).unwrap(), InputFormat::Xlsx);
+    assert_eq!(
+      format_of("b.dat", "品番,SZ".as_bytes()).unwrap(),
+      InputFormat::Csv
+    );
+    assert!(matches!(
+      format_of("c.xlsx", "品番,SZ".as_bytes()),
+      Err(Error::ExcelRead)
+    ));
+    assert!(matches!(
+      format_of("c.bin", &[1, 0, 2]),
+      Err(Error::UnsupportedInputFormat(extension)) if extension == "bin"
+    ));
+  }
+
+  #[test]
+  fn read_tab_separated_ragged_rows() {
+    let path = temp_file(
+      "ragged.tsv",
+      "品番\tSZ\t採寸\nA-1\t01\nB-2\t02\t肩幅:40\tmemo\n".as_bytes(),
+    );
+    let range = read_csv(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(range.get_size(), (3, 4));
+    let cell = |row: u32, col: u32| range.get_value((row, col)).map(|value| value.to_string());
+    assert_eq!(cell(0, 2).as_deref(), Some("採寸"));
+    assert_eq!(cell(1, 1).as_deref(), Some("01"));
+    assert_eq!(cell(1, 2).as_deref(), Some(""));
+    assert_eq!(cell(2, 2).as_deref(), Some("肩幅:40"));
+    assert_eq!(cell(2, 3).as_deref(), Some("memo"));
+  }
+
+  #[test]
+  fn decode_shift_jis_and_utf8() {
+    let text = "品番,SZ,採寸\nA-1,01,肩幅:40 着丈:60\n";
+    let (shift_jis, _, _) = encoding_rs::SHIFT_JIS.encode(text);
+    assert_eq!(decode_text(&shift_jis), text);
+    assert_eq!(decode_text(text.as_bytes()), text);
+
+    let mut with_bom = b"\xEF\xBB\xBF".to_vec();
+    with_bom.extend_from_slice(text.as_bytes());
+    assert_eq!(decode_text(&with_bom), text);
+  }
+}
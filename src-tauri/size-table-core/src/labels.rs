@@ -0,0 +1,57 @@
+use itertools::Itertools;
+use phdb_translate::TargetLanguage;
+
+use crate::models::ItemMeta;
+
+/// header of the first column, holding the size codes
+pub fn size_header(language: TargetLanguage) -> &'static str {
+  match language {
+    TargetLanguage::ZhHans => "尺码",
+    TargetLanguage::ZhHant => "尺碼",
+    TargetLanguage::Ko => "사이즈",
+    TargetLanguage::En => "Size",
+  }
+}
+
+/// title drawn above the table
+pub fn table_title(language: TargetLanguage) -> &'static str {
+  match language {
+    TargetLanguage::ZhHans => "尺码表",
+    TargetLanguage::ZhHant => "尺碼表",
+    TargetLanguage::Ko => "사이즈표",
+    TargetLanguage::En => "Size Chart",
+  }
+}
+
+/// label put before the item code, on pdf pages and in the single-sheet workbook
+pub fn item_code_label(language: TargetLanguage) -> &'static str {
+  match language {
+    TargetLanguage::ZhHans | TargetLanguage::ZhHant => "品番",
+    TargetLanguage::Ko => "품번",
+    TargetLanguage::En => "Item No.",
+  }
+}
+
+/// value of the html `lang` attribute
+pub fn html_lang(language: TargetLanguage) -> &'static str {
+  match language {
+    TargetLanguage::ZhHans => "zh-CN",
+    TargetLanguage::ZhHant => "zh-TW",
+    TargetLanguage::Ko => "ko",
+    TargetLanguage::En => "en",
+  }
+}
+
+/// title of a document holding every table of `item_meta`, such as "尺码表 / 사이즈표"
+pub fn document_title(item_meta: &[ItemMeta]) -> String {
+  let titles = item_meta
+    .iter()
+    .map(|meta| table_title(meta.language))
+    .unique()
+    .join(" / ");
+  if titles.is_empty() {
+    table_title(TargetLanguage::default()).to_string()
+  } else {
+    titles
+  }
+}
@@ -0,0 +1,214 @@
+use ab_glyph::{Font, FontRef, PxScale, ScaleFont};
+
+use crate::models::ItemTable;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+  /// `#rrggbb`, as used by svg and html
+  pub fn to_hex(self) -> String {
+    format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+  }
+}
+
+/// Sizes and colors of the size table, in css pixels
+///
+/// The defaults follow the `SizeTable` component of the webview
+#[derive(Debug, Clone)]
+pub struct TableStyle {
+  pub font_size: f32,
+  pub title_font_size: f32,
+  pub cell_padding: f32,
+  pub min_cell_width: f32,
+  pub border_width: f32,
+  pub margin: f32,
+  pub title_gap: f32,
+  pub border_color: Rgb,
+  pub head_color: Rgb,
+  pub text_color: Rgb,
+  pub background: Rgb,
+}
+
+impl Default for TableStyle {
+  fn default() -> Self {
+    Self {
+      font_size: 16.0,
+      title_font_size: 24.0,
+      cell_padding: 4.8,
+      min_cell_width: 48.0,
+      border_width: 1.0,
+      margin: 8.0,
+      title_gap: 4.0,
+      border_color: Rgb(0x9e, 0x2e, 0x26),
+      head_color: Rgb(0x9e, 0x2e, 0x26),
+      text_color: Rgb(0x2d, 0x2d, 0x2d),
+      background: Rgb(0xff, 0xff, 0xff),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextRole {
+  Title,
+  Head,
+  Body,
+}
+
+/// A single line of text placed on the table
+#[derive(Debug, Clone)]
+pub struct TextRun {
+  pub text: String,
+  pub role: TextRole,
+  /// left edge of the text
+  pub x: f32,
+  pub baseline: f32,
+  pub width: f32,
+  pub font_size: f32,
+  pub color: Rgb,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rect {
+  pub x: f32,
+  pub y: f32,
+  pub width: f32,
+  pub height: f32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Line {
+  pub x1: f32,
+  pub y1: f32,
+  pub x2: f32,
+  pub y2: f32,
+}
+
+/// Position of everything drawn for one size table, shared by every renderer
+/// so that images, svg and pdf look the same
+#[derive(Debug, Clone)]
+pub struct TableLayout {
+  pub width: f32,
+  pub height: f32,
+  pub texts: Vec<TextRun>,
+  /// outer border of the table
+  pub border: Rect,
+  /// separators between the cells
+  pub lines: Vec<Line>,
+  pub style: TableStyle,
+}
+
+/// width of `text` when drawn with `font` at `font_size`
+pub fn text_width(font: &FontRef, font_size: f32, text: &str) -> f32 {
+  let scaled = font.as_scaled(PxScale::from(font_size));
+  let mut width = 0.0;
+  let mut previous = None;
+  for c in text.chars() {
+    let id = scaled.glyph_id(c);
+    if let Some(previous) = previous {
+      width += scaled.kern(previous, id);
+    }
+    width += scaled.h_advance(id);
+    previous = Some(id);
+  }
+  width
+}
+
+/// `title` is drawn above the table, see [`crate::labels::table_title`]
+pub fn layout_table(
+  table: &ItemTable,
+  title: &str,
+  font: &FontRef,
+  style: &TableStyle,
+) -> TableLayout {
+  let columns = table
+    .body
+    .iter()
+    .map(|row| row.len())
+    .chain(std::iter::once(table.head.len()))
+    .max()
+    .unwrap_or(0);
+  let cell = |row: &[String], i: usize| row.get(i).cloned().unwrap_or_default();
+
+  let column_widths = (0..columns)
+    .map(|i| {
+      std::iter::once(&table.head)
+        .chain(table.body.iter())
+        .map(|row| text_width(font, style.font_size, &cell(row, i)) + style.cell_padding * 2.0)
+        .fold(style.min_cell_width, f32::max)
+    })
+    .collect::<Vec<_>>();
+
+  let scaled = font.as_scaled(PxScale::from(style.font_size));
+  let line_height = scaled.height() + scaled.line_gap();
+  let row_height = line_height + style.cell_padding * 2.0;
+  let table_width = column_widths.iter().sum::<f32>() + style.border_width * 2.0;
+  let table_height = row_height * (table.body.len() + 1) as f32 + style.border_width * 2.0;
+
+  let title_scaled = font.as_scaled(PxScale::from(style.title_font_size));
+  let title_width = text_width(font, style.title_font_size, title);
+  let title_height = title_scaled.height() + title_scaled.line_gap();
+
+  let content_width = table_width.max(title_width);
+  let width = content_width + style.margin * 2.0;
+  let height = style.margin * 2.0 + title_height + style.title_gap + table_height;
+
+  let mut texts = vec![TextRun {
+    text: title.to_string(),
+    role: TextRole::Title,
+    x: (width - title_width) / 2.0,
+    baseline: style.margin + title_scaled.ascent(),
+    width: title_width,
+    font_size: style.title_font_size,
+    color: style.text_color,
+  }];
+
+  let border = Rect {
+    x: (width - table_width) / 2.0,
+    y: style.margin + title_height + style.title_gap,
+    width: table_width,
+    height: table_height,
+  };
+
+  let rows = std::iter::once((TextRole::Head, &table.head))
+    .chain(table.body.iter().map(|row| (TextRole::Body, row)));
+  let mut row_top = border.y + style.border_width;
+  for (role, row) in rows {
+    let mut cell_left = border.x + style.border_width;
+    for (i, column_width) in column_widths.iter().enumerate() {
+      let text = cell(row, i);
+      let text_width = text_width(font, style.font_size, &text);
+      texts.push(TextRun {
+        x: cell_left + (column_width - text_width) / 2.0,
+        baseline: row_top + style.cell_padding + scaled.ascent(),
+        width: text_width,
+        font_size: style.font_size,
+        color: match role {
+          TextRole::Head => style.head_color,
+          _ => style.text_color,
+        },
+        role,
+        text,
+      });
+      cell_left += column_width;
+    }
+    row_top += row_height;
+  }
+
+  let head_bottom = border.y + style.border_width + row_height;
+  let lines = vec![Line {
+    x1: border.x,
+    y1: head_bottom,
+    x2: border.x + border.width,
+    y2: head_bottom,
+  }];
+
+  TableLayout {
+    width,
+    height,
+    texts,
+    border,
+    lines,
+    style: style.clone(),
+  }
+}
@@ -0,0 +1,222 @@
+//! Size table engine shared by the desktop app and the `stg` command line tool.
+//!
+//! Reads the [品番] [SZ] [採寸] columns of a workbook or CSV file, groups the rows by item code,
+//! parses the size texts and builds one [`ItemTable`] per item code.
+
+use itertools::Itertools;
+
+pub mod columns;
+pub mod config;
+pub mod excel_processor;
+pub mod export;
+pub mod fonts;
+pub mod html;
+pub mod input;
+pub mod labels;
+pub mod layout;
+pub mod measurement;
+pub mod models;
+pub mod pdf;
+pub mod project;
+pub mod render;
+pub mod size_order;
+pub mod size_parser;
+pub mod svg;
+pub mod synonyms;
+pub mod units;
+pub mod user_glossary;
+pub mod validation;
+pub mod xlsx;
+
+pub use columns::{Column, ColumnAliases, ColumnIndices};
+pub use config::{
+  load_config, open_translation_cache, Config, TranslatorBackend, TranslatorOptions,
+};
+pub use excel_processor::{
+  list_sheets, process_workbook, DuplicatePolicy, ProcessOptions, ProcessOutput, SheetInfo,
+  SheetSelection,
+};
+pub use measurement::{Amount, MeasurementValue, Unit};
+pub use models::{ItemInfo, ItemMeta, ItemTable};
+pub use size_order::SizeOrder;
+pub use size_parser::{SizeDetail, SizeDetails};
+pub use synonyms::MeasurementSynonyms;
+pub use user_glossary::{GlossaryEntry, UserGlossary};
+pub use validation::{
+  DuplicateRows, IssueKind, MeasurementMismatch, MissingMeasurements, RowIssue, UntranslatedTerm,
+  ValidationReport,
+};
+
+#[derive(Debug)]
+pub enum Error {
+  ExcelRead,
+  UnsupportedInputFormat(String),
+  EmptyFile,
+  InvalidSheetFormat,
+  SheetNotFound(String),
+  /// an error while processing one worksheet of the workbook
+  InSheet {
+    sheet: String,
+    error: Box<Error>,
+  },
+  MissingColumns(Vec<Column>),
+  /// (sheet column index, header) of every header standing for the column
+  DuplicateColumn {
+    column: Column,
+    headers: Vec<(usize, String)>,
+  },
+  InvalidItemCode,
+  InvalidSizeCode,
+  InvalidSizeText {
+    error_line: String,
+  },
+  MelroseType(melrose_types::error::Error),
+  EmptySizeText,
+  /// one (品番, SZ) pair has rows with different [採寸] under [`DuplicatePolicy::Fail`]
+  ConflictingDuplicates(Vec<DuplicateRows>),
+  Translation(phdb_translate::Error),
+  SystemIO(std::io::Error),
+  /// writing an exported table or a project file
+  FileWrite(std::io::Error),
+  SerdeJson(serde_json::Error),
+  Csv(csv::Error),
+  CsvRead(csv::Error),
+  UnsupportedExportFormat(String),
+  FontNotEmbedded,
+  Image(image::ImageError),
+  Pdf(printpdf::Error),
+  XlsxWrite(rust_xlsxwriter::XlsxError),
+  /// opening or parsing a project file
+  ProjectRead {
+    path: String,
+    error: std::io::Error,
+  },
+  UnsupportedProjectVersion(u32),
+  GlossaryEntryExists(String),
+  GlossaryEntryNotFound(String),
+  EmptyGlossaryEntry,
+  MissingDeepLApiKey,
+}
+
+impl From<phdb_translate::Error> for Error {
+  fn from(e: phdb_translate::Error) -> Self {
+    Self::Translation(e)
+  }
+}
+
+impl From<std::io::Error> for Error {
+  fn from(e: std::io::Error) -> Self {
+    Self::SystemIO(e)
+  }
+}
+
+impl From<serde_json::Error> for Error {
+  fn from(e: serde_json::Error) -> Self {
+    Self::SerdeJson(e)
+  }
+}
+
+impl From<csv::Error> for Error {
+  fn from(e: csv::Error) -> Self {
+    Self::Csv(e)
+  }
+}
+
+impl From<image::ImageError> for Error {
+  fn from(e: image::ImageError) -> Self {
+    Self::Image(e)
+  }
+}
+
+impl From<printpdf::Error> for Error {
+  fn from(e: printpdf::Error) -> Self {
+    Self::Pdf(e)
+  }
+}
+
+impl From<rust_xlsxwriter::XlsxError> for Error {
+  fn from(e: rust_xlsxwriter::XlsxError) -> Self {
+    Self::XlsxWrite(e)
+  }
+}
+
+impl From<melrose_types::error::Error> for Error {
+  fn from(e: melrose_types::error::Error) -> Self {
+    Self::MelroseType(e)
+  }
+}
+
+impl From<Error> for String {
+  fn from(e: Error) -> Self {
+    match e {
+      Error::EmptyFile => String::from("文件是空文件"),
+      Error::ExcelRead => String::from("请选择需要打开的Excel文件"),
+      Error::UnsupportedInputFormat(extension) => format!("不支持的文件格式:{extension}"),
+      Error::InvalidSheetFormat => String::from("请确认Excel文件有[品番][採寸]和[SZ]列"),
+      Error::SheetNotFound(sheet) => format!("找不到工作表[{sheet}]"),
+      Error::InSheet { sheet, error } => format!("[{sheet}]工作表:{}", String::from(*error)),
+      Error::MissingColumns(columns) => format!(
+        "找不到{}列，请确认表头，或在设定文件的columnAliases中添加别名",
+        columns.iter().map(|column| format!("[{column}]")).join("")
+      ),
+      Error::DuplicateColumn { column, headers } => format!(
+        "[{column}]列重复:{}",
+        headers
+          .iter()
+          .map(|(i, header)| format!("{}列「{header}」", columns::column_name(*i)))
+          .join(", ")
+      ),
+      Error::InvalidItemCode => String::from("品番格式错误"),
+      Error::InvalidSizeCode => String::from("SZ格式错误"),
+      Error::InvalidSizeText { error_line } => format!("採寸格式错误:{}", error_line),
+      Error::EmptySizeText => String::from("[採寸]列不能是空栏"),
+      Error::ConflictingDuplicates(conflicts) => format!(
+        "以下品番和SZ有多行且[採寸]不一致:{}",
+        conflicts
+          .iter()
+          .map(|conflict| format!(
+            "{} SZ {}({})",
+            conflict.item_code,
+            conflict.size_code,
+            conflict
+              .rows
+              .iter()
+              .zip(&conflict.values)
+              .map(|(row, value)| format!("第{row}行「{value}」"))
+              .join(" / ")
+          ))
+          .join(", ")
+      ),
+      Error::MelroseType(e) => match e {
+        melrose_types::error::Error::ItemCode(_) => String::from("品番格式错误"),
+        melrose_types::error::Error::SizeCode(_) => String::from("SZ格式错误"),
+        melrose_types::error::Error::ParseCommonError(e) => format!("格式错误:{e:?}"),
+        melrose_types::error::Error::ColorCode(_) => String::from("色番格式错误"),
+      },
+      Error::Translation(e) => format!("翻译失败:{e:?}"),
+      Error::SystemIO(_) => String::from("设定文件读取错误"),
+      Error::FileWrite(e) => format!("文件保存失败:{e}"),
+      Error::SerdeJson(e) => {
+        println!("error:{}", e);
+        String::from("设定文件解析错误")
+      }
+      Error::Csv(_) => String::from("CSV文件写入错误"),
+      Error::CsvRead(e) => format!("CSV文件读取错误:{e}"),
+      Error::UnsupportedExportFormat(format) => format!("不支持的导出格式:{format}"),
+      Error::FontNotEmbedded => String::from("字体文件未嵌入，无法生成图片和PDF"),
+      Error::Image(e) => format!("图片生成失败:{e}"),
+      Error::Pdf(e) => format!("PDF生成失败:{e:?}"),
+      Error::XlsxWrite(e) => format!("Excel文件写入错误:{e}"),
+      Error::ProjectRead { path, error } => format!("项目文件读取失败:{path} ({error})"),
+      Error::UnsupportedProjectVersion(version) => {
+        format!("项目文件版本过新(v{version})，请更新应用")
+      }
+      Error::GlossaryEntryExists(source) => format!("词汇表中已有「{source}」"),
+      Error::GlossaryEntryNotFound(source) => format!("词汇表中没有「{source}」"),
+      Error::EmptyGlossaryEntry => String::from("原文和译文都不能为空"),
+      Error::MissingDeepLApiKey => String::from("使用DeepL翻译需要在设定文件中填写deeplApiKey"),
+    }
+  }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,230 @@
+use std::{convert::Infallible, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// Units written after the measurement values
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Unit {
+  Cm,
+  Mm,
+  G,
+  Kg,
+  Inch,
+}
+
+/// spellings found in the sheets, longer ones first so "inch" wins over "in"
+const UNIT_SPELLINGS: &[(&str, Unit)] = &[
+  ("センチ", Unit::Cm),
+  ("インチ", Unit::Inch),
+  ("inch", Unit::Inch),
+  ("cm", Unit::Cm),
+  ("mm", Unit::Mm),
+  ("kg", Unit::Kg),
+  ("in", Unit::Inch),
+  ("g", Unit::G),
+  ("\"", Unit::Inch),
+];
+
+impl Unit {
+  pub fn symbol(self) -> &'static str {
+    match self {
+      Unit::Cm => "cm",
+      Unit::Mm => "mm",
+      Unit::G => "g",
+      Unit::Kg => "kg",
+      Unit::Inch => "inch",
+    }
+  }
+}
+
+impl fmt::Display for Unit {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.symbol())
+  }
+}
+
+/// A single value, or a range such as the waist of an elastic band
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Amount {
+  Number(f64),
+  Range { min: f64, max: f64 },
+}
+
+impl fmt::Display for Amount {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Amount::Number(number) => write!(f, "{number}"),
+      Amount::Range { min, max } => write!(f, "{min}~{max}"),
+    }
+  }
+}
+
+/// The value of a [採寸] entry, such as "42.5cm", "62~72" or "約225g"
+///
+/// Parsing never fails: text that holds no number keeps `amount` empty, and the
+/// original text is always kept in `raw`. Formatting writes the value back with ascii
+/// digits and one spelling per unit.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeasurementValue {
+  pub amount: Option<Amount>,
+  pub unit: Option<Unit>,
+  /// qualifier written before the number, such as "約"
+  pub prefix: Option<String>,
+  /// qualifier written after the unit, such as "(伸長時)"
+  pub suffix: Option<String>,
+  pub raw: String,
+}
+
+impl MeasurementValue {
+  pub fn parse(s: &str) -> Self {
+    let normalized = normalize(s);
+    let unparsed = Self {
+      amount: None,
+      unit: None,
+      prefix: None,
+      suffix: None,
+      raw: s.to_string(),
+    };
+    let Some(start) = normalized.find(|c: char| c.is_ascii_digit()) else {
+      return unparsed;
+    };
+    let (prefix, rest) = normalized.split_at(start);
+    let Some((min, rest)) = take_number(rest) else {
+      return unparsed;
+    };
+
+    let after_separator = rest.trim_start().strip_prefix('~').map(str::trim_start);
+    let (amount, rest) = match after_separator.and_then(take_number) {
+      Some((max, rest)) => (Amount::Range { min, max }, rest),
+      None => (Amount::Number(min), rest),
+    };
+
+    let rest = rest.trim_start();
+    // compared in place, as lowercasing can change the byte length of other characters
+    let (unit, suffix) = match UNIT_SPELLINGS.iter().find(|(spelling, _)| {
+      rest
+        .get(..spelling.len())
+        .is_some_and(|start| start.eq_ignore_ascii_case(spelling))
+    }) {
+      Some((spelling, unit)) => (Some(*unit), &rest[spelling.len()..]),
+      None => (None, rest),
+    };
+
+    let qualifier = |part: &str| Some(part.trim().to_string()).filter(|part| !part.is_empty());
+    Self {
+      amount: Some(amount),
+      unit,
+      prefix: qualifier(prefix),
+      suffix: qualifier(suffix),
+      raw: s.to_string(),
+    }
+  }
+}
+
+impl FromStr for MeasurementValue {
+  type Err = Infallible;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    Ok(Self::parse(s))
+  }
+}
+
+impl fmt::Display for MeasurementValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let Some(amount) = self.amount else {
+      return f.write_str(&self.raw);
+    };
+    if let Some(prefix) = &self.prefix {
+      f.write_str(prefix)?;
+    }
+    write!(f, "{amount}")?;
+    if let Some(unit) = self.unit {
+      write!(f, "{unit}")?;
+    }
+    if let Some(suffix) = &self.suffix {
+      f.write_str(suffix)?;
+    }
+    Ok(())
+  }
+}
+
+/// full width digits and range marks to ascii, so the parser only deals with one form
+fn normalize(s: &str) -> String {
+  s.trim()
+    .chars()
+    .map(|c| match c {
+      '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+      '．' => '.',
+      '〜' | '～' | '-' | '－' | '–' | '―' => '~',
+      'ｃ' => 'c',
+      'ｍ' => 'm',
+      'ｇ' => 'g',
+      'ｋ' => 'k',
+      '”' | '″' => '"',
+      c => c,
+    })
+    .collect()
+}
+
+/// a leading decimal number and the text after it
+fn take_number(s: &str) -> Option<(f64, &str)> {
+  let end = s
+    .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+    .unwrap_or(s.len());
+  let number = s[..end].trim_end_matches('.').parse().ok()?;
+  Some((number, &s[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse(s: &str) -> MeasurementValue {
+    MeasurementValue::parse(s)
+  }
+
+  #[test]
+  fn parse_measurement_values() {
+    let value = parse("42.5cm");
+    assert_eq!(value.amount, Some(Amount::Number(42.5)));
+    assert_eq!(value.unit, Some(Unit::Cm));
+    assert_eq!((value.prefix, value.suffix), (None, None));
+
+    let value = parse("62～72");
+    assert_eq!(
+      value.amount,
+      Some(Amount::Range {
+        min: 62.0,
+        max: 72.0
+      })
+    );
+    assert_eq!(value.unit, None);
+    assert_eq!(value.to_string(), "62~72");
+
+    let value = parse("約２２５ｇ");
+    assert_eq!(value.amount, Some(Amount::Number(225.0)));
+    assert_eq!(value.unit, Some(Unit::G));
+    assert_eq!(value.prefix.as_deref(), Some("約"));
+    assert_eq!(value.raw, "約２２５ｇ");
+    assert_eq!(value.to_string(), "約225g");
+
+    let value = parse("1.2kg(伸長時)");
+    assert_eq!(value.unit, Some(Unit::Kg));
+    assert_eq!(value.suffix.as_deref(), Some("(伸長時)"));
+    assert_eq!(value.to_string(), "1.2kg(伸長時)");
+
+    let value = parse("42.5CM");
+    assert_eq!(value.unit, Some(Unit::Cm));
+
+    // the kelvin sign lowercases to a shorter "k", and is not a unit
+    let value = parse("10\u{212A}g");
+    assert_eq!(value.unit, None);
+    assert_eq!(value.suffix.as_deref(), Some("\u{212A}g"));
+
+    let value = parse("フリー");
+    assert_eq!(value.amount, None);
+    assert_eq!(value.to_string(), "フリー");
+  }
+}
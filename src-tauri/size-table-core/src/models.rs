@@ -0,0 +1,42 @@
+use melrose_types::{ItemCode, SizeCode};
+use phdb_translate::TargetLanguage;
+use serde::{Deserialize, Serialize};
+
+use crate::size_parser::SizeDetails;
+
+#[derive(Clone)]
+pub struct ItemInfo {
+  pub item_code: ItemCode,
+  pub size_code: SizeCode,
+  pub size_text: SizeDetails,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemTable {
+  pub head: Vec<String>,
+  pub body: Vec<Vec<String>>,
+  /// whether each column holds lengths, decided from the Japanese measurement name
+  ///
+  /// Unitless values are only converted to inches in these columns. Empty in tables
+  /// saved before it was recorded.
+  #[serde(default)]
+  pub lengths: Vec<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemMeta {
+  pub code: String,
+  pub size_code: String,
+  /// name of the worksheet the rows were read from
+  #[serde(default)]
+  pub sheet: String,
+  /// the language the table was translated into
+  #[serde(default)]
+  pub language: TargetLanguage,
+  /// whether the lengths are in inches, in the extra table of [`InchMode::SeparateTable`]
+  ///
+  /// [`InchMode::SeparateTable`]: crate::units::InchMode::SeparateTable
+  #[serde(default)]
+  pub inch: bool,
+  pub table: ItemTable,
+}
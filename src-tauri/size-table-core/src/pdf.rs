@@ -0,0 +1,287 @@
+use std::{fs, io::Cursor, path::Path};
+
+use ab_glyph::FontRef;
+use printpdf::{
+  path::PaintMode, Color, IndirectFontRef, Line, Mm, PdfDocument, PdfDocumentReference,
+  PdfLayerReference, PdfPageIndex, Point, Rect, Rgb as PdfRgb,
+};
+
+use crate::{
+  fonts,
+  labels::{item_code_label, table_title},
+  layout::{layout_table, text_width, Rgb, TableLayout, TableStyle},
+  models::ItemMeta,
+  Error, Result,
+};
+
+/// A4 portrait, in mm
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const PAGE_MARGIN: f32 = 15.0;
+/// space between the item code header and the table
+const HEADER_HEIGHT: f32 = 12.0;
+const FOOTER_HEIGHT: f32 = 10.0;
+const HEADER_FONT_SIZE: f32 = 14.0;
+const FOOTER_FONT_SIZE: f32 = 9.0;
+/// the layout is in css pixels (96 dpi)
+const MM_PER_PX: f32 = 25.4 / 96.0;
+const PT_PER_PX: f32 = 0.75;
+
+/// One A4 page with the item code and its size table
+pub fn render_item_pdf(meta: &ItemMeta, style: &TableStyle) -> Result<Vec<u8>> {
+  let font = fonts::embedded_font()?;
+  let title = table_title(meta.language);
+  let (doc, pdf_font, _, layer) = new_document(&format!("{title} {}", meta.code))?;
+  let layout = layout_table(&meta.table, title, &font, style);
+  draw_item_page(&layer, &pdf_font, &font, meta, &layout, None);
+  Ok(doc.save_to_bytes()?)
+}
+
+/// Every item of a workbook in one document, one page per item code with page numbers
+pub fn render_catalog_pdf(
+  item_meta: &[ItemMeta],
+  title: &str,
+  style: &TableStyle,
+) -> Result<Vec<u8>> {
+  let font = fonts::embedded_font()?;
+  let (doc, pdf_font, first_page, first_layer) = new_document(title)?;
+  let total = item_meta.len();
+  for (i, meta) in item_meta.iter().enumerate() {
+    let (page, layer) = if i == 0 {
+      (first_page, first_layer.clone())
+    } else {
+      let (page, layer) = doc.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+      (page, doc.get_page(page).get_layer(layer))
+    };
+    let layout = layout_table(&meta.table, table_title(meta.language), &font, style);
+    draw_item_page(
+      &layer,
+      &pdf_font,
+      &font,
+      meta,
+      &layout,
+      Some((i + 1, total)),
+    );
+    doc.add_bookmark(meta.code.clone(), page);
+  }
+  Ok(doc.save_to_bytes()?)
+}
+
+pub fn write_item_pdf(meta: &ItemMeta, path: impl AsRef<Path>, style: &TableStyle) -> Result<()> {
+  fs::write(path, render_item_pdf(meta, style)?).map_err(Error::FileWrite)?;
+  Ok(())
+}
+
+pub fn write_catalog_pdf(
+  item_meta: &[ItemMeta],
+  title: &str,
+  path: impl AsRef<Path>,
+  style: &TableStyle,
+) -> Result<()> {
+  fs::write(path, render_catalog_pdf(item_meta, title, style)?).map_err(Error::FileWrite)?;
+  Ok(())
+}
+
+/// the font is embedded (and subset) so the pdf prints the same everywhere
+fn new_document(
+  title: &str,
+) -> Result<(
+  PdfDocumentReference,
+  IndirectFontRef,
+  PdfPageIndex,
+  PdfLayerReference,
+)> {
+  let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+  let pdf_font = doc.add_external_font(Cursor::new(fonts::font_data()?))?;
+  let layer = doc.get_page(page).get_layer(layer);
+  Ok((doc, pdf_font, page, layer))
+}
+
+fn pdf_color(color: Rgb) -> Color {
+  Color::Rgb(PdfRgb::new(
+    color.0 as f32 / 255.0,
+    color.1 as f32 / 255.0,
+    color.2 as f32 / 255.0,
+    None,
+  ))
+}
+
+fn draw_item_page(
+  layer: &PdfLayerReference,
+  pdf_font: &IndirectFontRef,
+  font: &FontRef,
+  meta: &ItemMeta,
+  layout: &TableLayout,
+  page_number: Option<(usize, usize)>,
+) {
+  let style = &layout.style;
+  layer.set_fill_color(pdf_color(style.text_color));
+  layer.use_text(
+    format!("{} {}", item_code_label(meta.language), meta.code),
+    HEADER_FONT_SIZE,
+    Mm(PAGE_MARGIN),
+    Mm(PAGE_HEIGHT - PAGE_MARGIN - HEADER_FONT_SIZE / PT_PER_PX * MM_PER_PX),
+    pdf_font,
+  );
+
+  // shrink tables that do not fit on the page, never enlarge them
+  let available_width = PAGE_WIDTH - PAGE_MARGIN * 2.0;
+  let available_height = PAGE_HEIGHT - PAGE_MARGIN * 2.0 - HEADER_HEIGHT - FOOTER_HEIGHT;
+  let scale = (available_width / (layout.width * MM_PER_PX))
+    .min(available_height / (layout.height * MM_PER_PX))
+    .min(1.0);
+  let left = (PAGE_WIDTH - layout.width * MM_PER_PX * scale) / 2.0;
+  let top = PAGE_HEIGHT - PAGE_MARGIN - HEADER_HEIGHT;
+  let x = |px: f32| Mm(left + px * MM_PER_PX * scale);
+  let y = |px: f32| Mm(top - px * MM_PER_PX * scale);
+
+  layer.set_outline_color(pdf_color(style.border_color));
+  layer.set_outline_thickness(style.border_width * PT_PER_PX * scale);
+  let half = style.border_width / 2.0;
+  let border = layout.border;
+  layer.add_rect(
+    Rect::new(
+      x(border.x + half),
+      y(border.y + border.height - half),
+      x(border.x + border.width - half),
+      y(border.y + half),
+    )
+    .with_mode(PaintMode::Stroke),
+  );
+  for line in &layout.lines {
+    layer.add_line(Line {
+      points: vec![
+        (Point::new(x(line.x1), y(line.y1 + half)), false),
+        (Point::new(x(line.x2), y(line.y2 + half)), false),
+      ],
+      is_closed: false,
+    });
+  }
+
+  for text in &layout.texts {
+    layer.set_fill_color(pdf_color(text.color));
+    layer.use_text(
+      text.text.clone(),
+      text.font_size * PT_PER_PX * scale,
+      x(text.x),
+      y(text.baseline),
+      pdf_font,
+    );
+  }
+
+  if let Some((current, total)) = page_number {
+    let footer = format!("{current} / {total}");
+    let footer_width = text_width(font, FOOTER_FONT_SIZE / PT_PER_PX, &footer) * MM_PER_PX;
+    layer.set_fill_color(pdf_color(style.text_color));
+    layer.use_text(
+      footer,
+      FOOTER_FONT_SIZE,
+      Mm((PAGE_WIDTH - footer_width) / 2.0),
+      Mm(PAGE_MARGIN),
+      pdf_font,
+    );
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::collections::HashMap;
+
+  use lopdf::{content::Content, Dictionary, Document, Object};
+
+  use super::*;
+  use crate::models::ItemTable;
+
+  fn meta(code: &str) -> ItemMeta {
+    ItemMeta {
+      code: code.to_string(),
+      size_code: "01".to_string(),
+      sheet: "Sheet1".to_string(),
+      language: Default::default(),
+      inch: false,
+      table: ItemTable {
+        head: vec!["尺码".to_string(), "肩宽".to_string()],
+        body: vec![vec!["S".to_string(), "42".to_string()]],
+        lengths: Vec::new(),
+      },
+    }
+  }
+
+  fn dict<'a>(doc: &'a Document, object: &'a Object) -> &'a Dictionary {
+    doc.dereference(object).unwrap().1.as_dict().unwrap()
+  }
+
+  /// the texts drawn on each page, decoded through the ToUnicode map of the font
+  fn page_texts(bytes: &[u8]) -> Vec<Vec<String>> {
+    let doc = Document::load_mem(bytes).unwrap();
+    doc
+      .get_pages()
+      .into_values()
+      .map(|page_id| {
+        // printpdf refers to the font dictionary, which get_page_fonts does not follow
+        let page = doc.get_dictionary(page_id).unwrap();
+        let resources = dict(&doc, page.get(b"Resources").unwrap());
+        let fonts = dict(&doc, resources.get(b"Font").unwrap());
+        let (_, font) = fonts.iter().next().unwrap();
+        let to_unicode = dict(&doc, font)
+          .get(b"ToUnicode")
+          .and_then(Object::as_reference)
+          .unwrap();
+        let cmap = doc
+          .get_object(to_unicode)
+          .and_then(Object::as_stream)
+          .unwrap();
+        let cmap = cmap
+          .decompressed_content()
+          .unwrap_or_else(|_| cmap.content.clone());
+        let glyphs = String::from_utf8_lossy(&cmap)
+          .lines()
+          .filter_map(|line| {
+            let (glyph, unicode) = line.strip_prefix('<')?.split_once("> <")?;
+            let unicode = u32::from_str_radix(unicode.strip_suffix('>')?, 16).ok()?;
+            Some((
+              u16::from_str_radix(glyph, 16).ok()?,
+              char::from_u32(unicode)?,
+            ))
+          })
+          .collect::<HashMap<_, _>>();
+        let content = Content::decode(&doc.get_page_content(page_id).unwrap()).unwrap();
+        content
+          .operations
+          .iter()
+          .filter(|operation| operation.operator == "Tj")
+          .filter_map(|operation| operation.operands[0].as_str().ok())
+          .map(|bytes| {
+            bytes
+              .chunks(2)
+              .map(|pair| glyphs[&u16::from_be_bytes([pair[0], pair[1]])])
+              .collect()
+          })
+          .collect()
+      })
+      .collect()
+  }
+
+  #[test]
+  fn catalog_pages() {
+    let item_meta = [meta("A-1"), meta("B-2")];
+    let bytes = render_catalog_pdf(&item_meta, "尺码表", &TableStyle::default()).unwrap();
+    let pages = page_texts(&bytes);
+    assert_eq!(pages.len(), 2);
+    for (i, (texts, code)) in pages.iter().zip(["A-1", "B-2"]).enumerate() {
+      assert_eq!(texts[0], format!("品番 {code}"));
+      assert_eq!(texts.last().unwrap(), &format!("{} / 2", i + 1));
+      assert!(texts.contains(&"肩宽".to_string()));
+    }
+  }
+
+  #[test]
+  fn item_page() {
+    let bytes = render_item_pdf(&meta("A-1"), &TableStyle::default()).unwrap();
+    let pages = page_texts(&bytes);
+    assert_eq!(pages.len(), 1);
+    assert_eq!(pages[0][0], "品番 A-1");
+    assert!(pages[0].contains(&"尺码表".to_string()));
+    assert!(pages[0].contains(&"42".to_string()));
+  }
+}
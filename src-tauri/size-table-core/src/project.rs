@@ -0,0 +1,223 @@
+use std::{
+  fs::{self, File},
+  io::{BufReader, BufWriter},
+  path::Path,
+};
+
+use itertools::Itertools;
+use phdb_translate::{TargetLanguage, TranslateClient};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{models::ItemMeta, Error, Result};
+
+/// bump when the layout of [`ProjectFile`] changes
+pub const PROJECT_FILE_VERSION: u32 = 1;
+
+/// A processing run saved to disk, so it can be reopened and exported again
+/// without reading the workbook
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectFile {
+  pub version: u32,
+  pub source: SourceFile,
+  /// [`glossary_version`] at processing time
+  pub glossary_version: String,
+  /// the tables as generated, before any manual edit
+  pub item_meta: Vec<ItemMeta>,
+  pub edits: Vec<CellEdit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceFile {
+  pub path: String,
+  /// sha256 of the workbook, to notice when it changed after processing
+  pub sha256: String,
+}
+
+/// A cell changed by hand after processing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CellEdit {
+  /// worksheet of the table, as the same item code may be on several sheets
+  ///
+  /// empty in projects saved before it was recorded, matching the first table of `code`
+  #[serde(default)]
+  pub sheet: String,
+  /// item code of the table
+  pub code: String,
+  /// language of the table, as every language of an item has its own
+  #[serde(default)]
+  pub language: TargetLanguage,
+  /// 0 is the head row, 1.. are the body rows
+  pub row: usize,
+  pub column: usize,
+  pub value: String,
+}
+
+impl ProjectFile {
+  pub fn new(
+    source_path: impl AsRef<Path>,
+    glossary_version: impl Into<String>,
+    item_meta: Vec<ItemMeta>,
+    edits: Vec<CellEdit>,
+  ) -> Result<Self> {
+    let source_path = source_path.as_ref();
+    Ok(Self {
+      version: PROJECT_FILE_VERSION,
+      source: SourceFile {
+        path: source_path.display().to_string(),
+        sha256: file_sha256(source_path)?,
+      },
+      glossary_version: glossary_version.into(),
+      item_meta,
+      edits,
+    })
+  }
+
+  pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+    let file = File::create(path).map_err(Error::FileWrite)?;
+    serde_json::to_writer_pretty(BufWriter::new(file), self)
+      .map_err(|e| Error::FileWrite(e.into()))?;
+    Ok(())
+  }
+
+  pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+    let path = path.as_ref();
+    let read_error = |error| Error::ProjectRead {
+      path: path.display().to_string(),
+      error,
+    };
+    let file = File::open(path).map_err(read_error)?;
+    let project: Self =
+      serde_json::from_reader(BufReader::new(file)).map_err(|e| read_error(e.into()))?;
+    if project.version > PROJECT_FILE_VERSION {
+      return Err(Error::UnsupportedProjectVersion(project.version));
+    }
+    Ok(project)
+  }
+
+  /// the tables with every manual edit applied
+  pub fn edited_item_meta(&self) -> Vec<ItemMeta> {
+    let mut item_meta = self.item_meta.clone();
+    for edit in &self.edits {
+      let Some(meta) = item_meta.iter_mut().find(|meta| {
+        meta.code == edit.code
+          && meta.language == edit.language
+          && (edit.sheet.is_empty() || meta.sheet == edit.sheet)
+      }) else {
+        continue;
+      };
+      let row = match edit.row {
+        0 => Some(&mut meta.table.head),
+        i => meta.table.body.get_mut(i - 1),
+      };
+      if let Some(cell) = row.and_then(|row| row.get_mut(edit.column)) {
+        cell.clone_from(&edit.value);
+      }
+    }
+    item_meta
+  }
+
+  /// whether the workbook is missing or differs from the one that was processed
+  pub fn source_changed(&self) -> bool {
+    file_sha256(&self.source.path)
+      .map(|sha256| sha256 != self.source.sha256)
+      .unwrap_or(true)
+  }
+}
+
+/// [`TranslateClient::glossary_version`] of every language of the tables, joined by ","
+///
+/// Simplified Chinese tables alone give the version of its glossary, as project files
+/// saved before the other languages have.
+pub fn glossary_version(client: &TranslateClient, item_meta: &[ItemMeta]) -> String {
+  let languages = item_meta
+    .iter()
+    .map(|meta| meta.language)
+    .unique()
+    .collect_vec();
+  if languages.is_empty() {
+    return client.glossary_version(Default::default());
+  }
+  languages
+    .into_iter()
+    .map(|language| client.glossary_version(language))
+    .join(",")
+}
+
+fn file_sha256(path: impl AsRef<Path>) -> Result<String> {
+  let digest = Sha256::digest(fs::read(path)?);
+  Ok(digest.iter().map(|b| format!("{b:02x}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::models::ItemTable;
+
+  #[test]
+  fn apply_edits() {
+    let project = ProjectFile {
+      version: PROJECT_FILE_VERSION,
+      source: SourceFile {
+        path: String::new(),
+        sha256: String::new(),
+      },
+      glossary_version: String::new(),
+      item_meta: ["Sheet1", "Sheet2"]
+        .into_iter()
+        .map(|sheet| ItemMeta {
+          code: "A-1".to_string(),
+          size_code: "01".to_string(),
+          sheet: sheet.to_string(),
+          language: Default::default(),
+          inch: false,
+          table: ItemTable {
+            head: vec!["尺码".to_string(), "肩宽".to_string()],
+            body: vec![vec!["S".to_string(), "42".to_string()]],
+            lengths: Vec::new(),
+          },
+        })
+        .collect(),
+      edits: vec![
+        CellEdit {
+          sheet: String::new(),
+          code: "A-1".to_string(),
+          language: Default::default(),
+          row: 0,
+          column: 1,
+          value: "肩宽(cm)".to_string(),
+        },
+        CellEdit {
+          sheet: "Sheet2".to_string(),
+          code: "A-1".to_string(),
+          language: Default::default(),
+          row: 1,
+          column: 1,
+          value: "42.5".to_string(),
+        },
+        CellEdit {
+          sheet: "Sheet1".to_string(),
+          code: "B-2".to_string(),
+          language: Default::default(),
+          row: 1,
+          column: 1,
+          value: "ignored".to_string(),
+        },
+      ],
+    };
+    let edited = project.edited_item_meta();
+    assert_eq!(edited[0].table.head[1], "肩宽(cm)");
+    assert_eq!(edited[0].table.body[0][1], "42");
+    assert_eq!(edited[1].table.head[1], "肩宽");
+    assert_eq!(edited[1].table.body[0][1], "42.5");
+    assert_eq!(project.item_meta[1].table.body[0][1], "42");
+  }
+
+  #[test]
+  fn missing_project_file() {
+    let path = std::env::temp_dir().join("stg-missing-project.json");
+    let error = ProjectFile::load(&path).unwrap_err();
+    assert!(matches!(&error, Error::ProjectRead { .. }));
+    assert!(String::from(error).contains("stg-missing-project.json"));
+  }
+}
@@ -0,0 +1,260 @@
+use std::{fs, io::Cursor, path::Path};
+
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use image::{codecs::jpeg::JpegEncoder, ImageFormat, Rgb as Pixel, RgbImage};
+
+use crate::{
+  fonts,
+  layout::{layout_table, Line, Rect, Rgb, TableLayout, TableStyle, TextRun},
+  models::ItemTable,
+  Error, Result,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RasterFormat {
+  Png,
+  Jpeg,
+}
+
+impl RasterFormat {
+  pub fn extension(self) -> &'static str {
+    match self {
+      Self::Png => "png",
+      Self::Jpeg => "jpg",
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderOptions {
+  pub style: TableStyle,
+  /// pixels per css pixel, like the device pixel ratio html2canvas used to pick up
+  pub scale: f32,
+  pub jpeg_quality: u8,
+}
+
+impl Default for RenderOptions {
+  fn default() -> Self {
+    Self {
+      style: TableStyle::default(),
+      scale: 2.0,
+      jpeg_quality: 95,
+    }
+  }
+}
+
+/// Draw the size table with the embedded font
+///
+/// Nothing from the system (fonts, webview, gpu) is involved, so the same table
+/// gives the same pixels on every machine
+pub fn render_table(table: &ItemTable, title: &str, options: &RenderOptions) -> Result<RgbImage> {
+  let font = fonts::embedded_font()?;
+  let layout = layout_table(table, title, &font, &options.style);
+  Ok(rasterize(&layout, &font, options.scale))
+}
+
+pub fn encode_table(
+  table: &ItemTable,
+  title: &str,
+  format: RasterFormat,
+  options: &RenderOptions,
+) -> Result<Vec<u8>> {
+  let image = render_table(table, title, options)?;
+  let mut buf = Vec::new();
+  match format {
+    RasterFormat::Png => image.write_to(&mut Cursor::new(&mut buf), ImageFormat::Png)?,
+    RasterFormat::Jpeg => image.write_with_encoder(JpegEncoder::new_with_quality(
+      &mut buf,
+      options.jpeg_quality,
+    ))?,
+  }
+  Ok(buf)
+}
+
+pub fn write_table_image(
+  table: &ItemTable,
+  title: &str,
+  path: impl AsRef<Path>,
+  format: RasterFormat,
+  options: &RenderOptions,
+) -> Result<()> {
+  fs::write(path, encode_table(table, title, format, options)?).map_err(Error::FileWrite)?;
+  Ok(())
+}
+
+fn rasterize(layout: &TableLayout, font: &FontRef, scale: f32) -> RgbImage {
+  let width = (layout.width * scale).ceil() as u32;
+  let height = (layout.height * scale).ceil() as u32;
+  let mut image = RgbImage::from_pixel(width, height, to_pixel(layout.style.background));
+
+  let border_width = (layout.style.border_width * scale).round().max(1.0) as u32;
+  draw_rect_outline(
+    &mut image,
+    layout.border,
+    scale,
+    border_width,
+    layout.style.border_color,
+  );
+  for line in &layout.lines {
+    draw_line(
+      &mut image,
+      *line,
+      scale,
+      border_width,
+      layout.style.border_color,
+    );
+  }
+  for text in &layout.texts {
+    draw_text(&mut image, font, text, scale);
+  }
+  image
+}
+
+fn to_pixel(color: Rgb) -> Pixel<u8> {
+  Pixel([color.0, color.1, color.2])
+}
+
+fn fill_rect(image: &mut RgbImage, x: u32, y: u32, width: u32, height: u32, color: Rgb) {
+  let x_end = (x + width).min(image.width());
+  let y_end = (y + height).min(image.height());
+  for py in y..y_end {
+    for px in x..x_end {
+      image.put_pixel(px, py, to_pixel(color));
+    }
+  }
+}
+
+fn draw_rect_outline(image: &mut RgbImage, rect: Rect, scale: f32, thickness: u32, color: Rgb) {
+  let x = (rect.x * scale).round() as u32;
+  let y = (rect.y * scale).round() as u32;
+  let width = (rect.width * scale).round() as u32;
+  let height = (rect.height * scale).round() as u32;
+  fill_rect(image, x, y, width, thickness, color);
+  fill_rect(image, x, y + height - thickness, width, thickness, color);
+  fill_rect(image, x, y, thickness, height, color);
+  fill_rect(image, x + width - thickness, y, thickness, height, color);
+}
+
+/// only horizontal and vertical lines are needed for tables
+fn draw_line(image: &mut RgbImage, line: Line, scale: f32, thickness: u32, color: Rgb) {
+  let x1 = (line.x1.min(line.x2) * scale).round() as u32;
+  let y1 = (line.y1.min(line.y2) * scale).round() as u32;
+  let x2 = (line.x1.max(line.x2) * scale).round() as u32;
+  let y2 = (line.y1.max(line.y2) * scale).round() as u32;
+  fill_rect(
+    image,
+    x1,
+    y1,
+    (x2 - x1).max(thickness),
+    (y2 - y1).max(thickness),
+    color,
+  );
+}
+
+fn draw_text(image: &mut RgbImage, font: &FontRef, text: &TextRun, scale: f32) {
+  let px_scale = PxScale::from(text.font_size * scale);
+  let scaled = font.as_scaled(px_scale);
+  let mut caret = text.x * scale;
+  let baseline = text.baseline * scale;
+  let mut previous = None;
+  for c in text.text.chars() {
+    let id = scaled.glyph_id(c);
+    if let Some(previous) = previous {
+      caret += scaled.kern(previous, id);
+    }
+    let glyph = id.with_scale_and_position(px_scale, point(caret, baseline));
+    caret += scaled.h_advance(id);
+    previous = Some(id);
+
+    let Some(outlined) = font.outline_glyph(glyph) else {
+      continue;
+    };
+    let bounds = outlined.px_bounds();
+    outlined.draw(|x, y, coverage| {
+      let px = bounds.min.x as i32 + x as i32;
+      let py = bounds.min.y as i32 + y as i32;
+      if px < 0 || py < 0 || px >= image.width() as i32 || py >= image.height() as i32 {
+        return;
+      }
+      let background = *image.get_pixel(px as u32, py as u32);
+      image.put_pixel(
+        px as u32,
+        py as u32,
+        blend(background, text.color, coverage),
+      );
+    });
+  }
+}
+
+fn blend(background: Pixel<u8>, color: Rgb, coverage: f32) -> Pixel<u8> {
+  let coverage = coverage.clamp(0.0, 1.0);
+  let mix = |bg: u8, fg: u8| (bg as f32 + (fg as f32 - bg as f32) * coverage).round() as u8;
+  Pixel([
+    mix(background[0], color.0),
+    mix(background[1], color.1),
+    mix(background[2], color.2),
+  ])
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn table() -> ItemTable {
+    ItemTable {
+      head: vec!["尺码".to_string(), "肩宽".to_string()],
+      body: vec![
+        vec!["S".to_string(), "42".to_string()],
+        vec!["M".to_string(), "44".to_string()],
+      ],
+      lengths: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn same_png_bytes() {
+    let options = RenderOptions::default();
+    let first = encode_table(&table(), "尺码表", RasterFormat::Png, &options).unwrap();
+    let second = encode_table(&table(), "尺码表", RasterFormat::Png, &options).unwrap();
+    assert_eq!(first, second);
+  }
+
+  #[test]
+  fn border_and_head_line() {
+    let options = RenderOptions::default();
+    let style = &options.style;
+    let scale = options.scale;
+    let font = fonts::embedded_font().unwrap();
+    let layout = layout_table(&table(), "尺码表", &font, style);
+    let image = render_table(&table(), "尺码表", &options).unwrap();
+    assert_eq!(
+      image.dimensions(),
+      (
+        (layout.width * scale).ceil() as u32,
+        (layout.height * scale).ceil() as u32
+      )
+    );
+
+    let border = to_pixel(style.border_color);
+    let left = (layout.border.x * scale).round() as u32;
+    let top = (layout.border.y * scale).round() as u32;
+    let right = left + (layout.border.width * scale).round() as u32 - 1;
+    let bottom = top + (layout.border.height * scale).round() as u32 - 1;
+    let middle_x = (left + right) / 2;
+    let middle_y = (top + bottom) / 2;
+    for (x, y) in [
+      (left, middle_y),
+      (right, middle_y),
+      (middle_x, top),
+      (middle_x, bottom),
+    ] {
+      assert_eq!(*image.get_pixel(x, y), border, "border at ({x}, {y})");
+    }
+    let head_line = (layout.lines[0].y1 * scale).round() as u32;
+    assert_eq!(*image.get_pixel(middle_x, head_line), border);
+    // the margin and the inside of the cells stay blank
+    let background = to_pixel(style.background);
+    assert_eq!(*image.get_pixel(0, 0), background);
+    assert_eq!(*image.get_pixel(left + 3, top + 3), background);
+  }
+}
@@ -0,0 +1,89 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::ItemInfo;
+
+/// The order sizes are listed in, smallest first
+///
+/// Sizes in the sequence come first in that order, then numeric sizes from small to
+/// large, then anything else by its text. Matching ignores ascii case.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SizeOrder(pub Vec<String>);
+
+impl Default for SizeOrder {
+  fn default() -> Self {
+    Self(
+      ["XXS", "XS", "S", "M", "L", "XL", "XXL", "3L", "4L", "F"]
+        .iter()
+        .map(|size| size.to_string())
+        .collect(),
+    )
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub enum SizeKey {
+  Listed(usize),
+  Numeric(f64),
+  Other(String),
+}
+
+impl SizeOrder {
+  /// sort key of a size, from its [SZ] code and the label shown in the table
+  pub fn key(&self, code: &str, label: &str) -> SizeKey {
+    let (code, label) = (code.trim(), label.trim());
+    let listed = |size: &str| {
+      self
+        .0
+        .iter()
+        .position(|listed| listed.trim().eq_ignore_ascii_case(size))
+    };
+    if let Some(position) = listed(label).or_else(|| listed(code)) {
+      return SizeKey::Listed(position);
+    }
+    match code.parse().or_else(|_| label.parse()) {
+      Ok(number) => SizeKey::Numeric(number),
+      Err(_) => SizeKey::Other(code.to_string()),
+    }
+  }
+
+  /// sort the rows of one item from the smallest size to the largest
+  pub fn sort(&self, item_infos: &mut [ItemInfo]) {
+    let key = |item_info: &ItemInfo| {
+      self.key(
+        &item_info.size_code.to_string(),
+        &item_info.size_code.to_roman_numeral(),
+      )
+    };
+    item_infos.sort_by(|a, b| key(a).partial_cmp(&key(b)).unwrap_or(Ordering::Equal));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn smallest_to_largest() {
+    let order = SizeOrder::default();
+    let mut sizes = [
+      ("L", "L"),
+      ("02", "II"),
+      ("xs", "xs"),
+      ("F", "F"),
+      ("10", "X"),
+      ("M", "M"),
+    ]
+    .map(|(code, label)| (order.key(code, label), code));
+    sizes.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+    assert_eq!(
+      sizes.map(|(_, code)| code),
+      ["xs", "M", "L", "F", "02", "10"]
+    );
+
+    let custom = SizeOrder(vec!["F".to_string(), "S".to_string()]);
+    assert!(custom.key("F", "F") < custom.key("S", "S"));
+  }
+}
@@ -0,0 +1,160 @@
+use std::{collections::HashMap, str::FromStr};
+
+use itertools::Itertools;
+
+use crate::measurement::MeasurementValue;
+use crate::synonyms::MeasurementSynonyms;
+use crate::Error;
+
+#[derive(Clone)]
+pub struct SizeDetail {
+  pub name: String,
+  /// the value as written in the sheet
+  pub value: String,
+  /// `value` parsed into number, unit and qualifier
+  pub measurement: MeasurementValue,
+}
+
+impl FromStr for SizeDetail {
+  type Err = Error;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    if s.is_empty() {
+      return Err(Error::EmptySizeText);
+    }
+
+    let escaped = escape_colon_whitespace(s);
+    // should contain ':' and only one ':'
+    if !escaped.contains(':') || escaped.matches(':').count() != 1 {
+      return Err(Error::InvalidSizeText {
+        error_line: escaped,
+      });
+    }
+
+    let name_value_pair = escaped.split(':').collect_vec();
+    if name_value_pair.len() != 2 {
+      return Err(Error::InvalidSizeText {
+        error_line: escaped,
+      });
+    }
+
+    if name_value_pair[0].is_empty() || name_value_pair[1].is_empty() {
+      return Err(Error::InvalidSizeText {
+        error_line: escaped,
+      });
+    }
+
+    let value = name_value_pair[1].to_string();
+    Ok(Self {
+      name: name_value_pair[0].to_string(),
+      measurement: MeasurementValue::parse(&value),
+      value,
+    })
+  }
+}
+
+/// A Contain the multi name value pairs of size detail separated by whitespace
+/// For instance: 肩宽:42.5cm 袖丈:62cm 胸囲:104cm 裾囲:104cm
+#[derive(Clone)]
+pub struct SizeDetails(pub Vec<SizeDetail>);
+
+impl FromStr for SizeDetails {
+  type Err = Error;
+
+  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    if s.is_empty() {
+      return Err(Error::EmptySizeText);
+    }
+
+    let splitted = s.split_whitespace().collect_vec();
+    if splitted.is_empty() {
+      return Err(Error::EmptySizeText);
+    }
+
+    let size_details = splitted
+      .iter()
+      .map(|s| SizeDetail::from_str(s))
+      .collect::<std::result::Result<Vec<_>, Error>>()?;
+
+    Ok(Self(size_details))
+  }
+}
+
+impl SizeDetails {
+  pub fn names(&self) -> Vec<String> {
+    self.0.iter().map(|sd| sd.name.to_owned()).collect()
+  }
+
+  pub fn values(&self) -> Vec<String> {
+    self.0.iter().map(|sd| sd.value.to_owned()).collect()
+  }
+
+  /// the measurement called `name`, the first one if it is listed twice
+  pub fn get(&self, name: &str) -> Option<&SizeDetail> {
+    self.0.iter().find(|sd| sd.name == name)
+  }
+
+  /// spell every name the canonical way, so synonyms share one header and glossary entry
+  pub fn normalize_names(&mut self, synonyms: &MeasurementSynonyms) {
+    for sd in self.0.iter_mut() {
+      sd.name = synonyms.canonical(&sd.name);
+    }
+  }
+
+  /// rename the measurements found in `names`, such as Japanese names to their translation
+  pub fn rename(&mut self, names: &HashMap<String, String>) {
+    for sd in self.0.iter_mut() {
+      if let Some(name) = names.get(&sd.name) {
+        sd.name = name.clone();
+      }
+    }
+  }
+}
+
+#[inline]
+fn escape_colon_whitespace(s: impl AsRef<str>) -> String {
+  s.as_ref()
+    .replace('：', ":")
+    .replace(": ", ":")
+    .replace('　', " ")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_size_details() {
+    let details: SizeDetails = "肩幅:42.5cm 袖丈：62cm　胸囲:104cm".parse().unwrap();
+    assert_eq!(details.names(), vec!["肩幅", "袖丈", "胸囲"]);
+    assert_eq!(details.values(), vec!["42.5cm", "62cm", "104cm"]);
+    assert_eq!(
+      details.get("袖丈").map(|sd| sd.value.as_str()),
+      Some("62cm")
+    );
+    assert!(details.get("着丈").is_none());
+    assert_eq!(details.0[0].measurement.to_string(), "42.5cm");
+
+    let mut details: SizeDetails = "肩巾:42.5cm 総丈:70cm".parse().unwrap();
+    details.normalize_names(&MeasurementSynonyms::default());
+    assert_eq!(details.names(), vec!["肩幅", "着丈"]);
+    details.rename(&HashMap::from([("肩幅".to_string(), "肩宽".to_string())]));
+    assert_eq!(details.names(), vec!["肩宽", "着丈"]);
+  }
+
+  #[test]
+  fn reject_invalid_size_text() {
+    assert!(matches!(
+      "肩幅42.5cm".parse::<SizeDetails>(),
+      Err(Error::InvalidSizeText { .. })
+    ));
+    assert!(matches!(
+      "肩幅:42:5".parse::<SizeDetails>(),
+      Err(Error::InvalidSizeText { .. })
+    ));
+    assert!(matches!(
+      "".parse::<SizeDetails>(),
+      Err(Error::EmptySizeText)
+    ));
+  }
+}
@@ -0,0 +1,173 @@
+use std::{fmt::Write as _, fs, path::Path};
+
+use crate::{
+  fonts,
+  layout::{layout_table, TableLayout, TableStyle},
+  models::ItemTable,
+  Error, Result,
+};
+
+/// Build an svg document of the size table
+///
+/// Text stays as `<text>` elements, so it can still be edited in Illustrator
+/// or the marketplace editors. The embedded font is only used to measure the text.
+pub fn render_svg(table: &ItemTable, title: &str, style: &TableStyle) -> Result<String> {
+  let font = fonts::embedded_font()?;
+  let layout = layout_table(table, title, &font, style);
+  Ok(layout_to_svg(&layout))
+}
+
+pub fn write_svg(
+  table: &ItemTable,
+  title: &str,
+  path: impl AsRef<Path>,
+  style: &TableStyle,
+) -> Result<()> {
+  fs::write(path, render_svg(table, title, style)?).map_err(Error::FileWrite)?;
+  Ok(())
+}
+
+fn layout_to_svg(layout: &TableLayout) -> String {
+  let style = &layout.style;
+  let mut svg = String::new();
+  // String への書き込みは失敗しない
+  let _ = writeln!(svg, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+  let _ = writeln!(
+    svg,
+    r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+    w = num(layout.width),
+    h = num(layout.height),
+  );
+  let _ = writeln!(
+    svg,
+    r#"  <rect width="100%" height="100%" fill="{}"/>"#,
+    style.background.to_hex()
+  );
+
+  // stroke is centered on the path, so move it inside the table box
+  let half = style.border_width / 2.0;
+  let _ = writeln!(
+    svg,
+    r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+    num(layout.border.x + half),
+    num(layout.border.y + half),
+    num(layout.border.width - style.border_width),
+    num(layout.border.height - style.border_width),
+    style.border_color.to_hex(),
+    num(style.border_width),
+  );
+  for line in &layout.lines {
+    let _ = writeln!(
+      svg,
+      r#"  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}"/>"#,
+      num(line.x1),
+      num(line.y1 + half),
+      num(line.x2),
+      num(line.y2 + half),
+      style.border_color.to_hex(),
+      num(style.border_width),
+    );
+  }
+
+  for text in &layout.texts {
+    let _ = writeln!(
+      svg,
+      r#"  <text x="{}" y="{}" text-anchor="middle" font-family="'{}', sans-serif" font-size="{}" fill="{}">{}</text>"#,
+      num(text.x + text.width / 2.0),
+      num(text.baseline),
+      fonts::FONT_FAMILY,
+      num(text.font_size),
+      text.color.to_hex(),
+      escape_xml(&text.text),
+    );
+  }
+  svg.push_str("</svg>\n");
+  svg
+}
+
+/// keep the coordinates short and stable
+pub(crate) fn num(v: f32) -> String {
+  let rounded = format!("{:.2}", v);
+  rounded
+    .trim_end_matches('0')
+    .trim_end_matches('.')
+    .to_string()
+}
+
+pub(crate) fn escape_xml(s: &str) -> String {
+  s.replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+    .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn escape_text() {
+    assert_eq!(
+      escape_xml("胸囲<104>&\"S\""),
+      "胸囲&lt;104&gt;&amp;&quot;S&quot;"
+    );
+  }
+
+  #[test]
+  fn every_cell_as_text() {
+    let table = ItemTable {
+      head: vec!["尺码".to_string(), "肩宽".to_string()],
+      body: vec![vec!["S".to_string(), "<42>".to_string()]],
+      lengths: Vec::new(),
+    };
+    let style = TableStyle::default();
+    let svg = render_svg(&table, "尺码表", &style).unwrap();
+    // the raster renderer draws the same layout
+    let font = fonts::embedded_font().unwrap();
+    let layout = layout_table(&table, "尺码表", &font, &style);
+
+    assert!(svg.contains(&format!(
+      r#"width="{w}" height="{h}" viewBox="0 0 {w} {h}""#,
+      w = num(layout.width),
+      h = num(layout.height)
+    )));
+    for cell in ["尺码表", "尺码", "肩宽", "S", "&lt;42&gt;"] {
+      assert!(svg.contains(&format!(">{cell}</text>")), "missing {cell}");
+    }
+    assert!(!svg.contains("<42>"));
+    assert_eq!(svg.matches("<text ").count(), layout.texts.len());
+    for text in &layout.texts {
+      let position = format!(
+        r#"<text x="{}" y="{}""#,
+        num(text.x + text.width / 2.0),
+        num(text.baseline)
+      );
+      assert!(svg.contains(&position), "{} not at {position}", text.text);
+    }
+
+    let half = style.border_width / 2.0;
+    assert!(svg.contains(&format!(
+      r#"<rect x="{}" y="{}" width="{}" height="{}" fill="none""#,
+      num(layout.border.x + half),
+      num(layout.border.y + half),
+      num(layout.border.width - style.border_width),
+      num(layout.border.height - style.border_width),
+    )));
+    let head_line = &layout.lines[0];
+    assert!(svg.contains(&format!(
+      r#"<line x1="{}" y1="{}" x2="{}" y2="{}""#,
+      num(head_line.x1),
+      num(head_line.y1 + half),
+      num(head_line.x2),
+      num(head_line.y2 + half),
+    )));
+  }
+
+  #[test]
+  fn format_number() {
+    assert_eq!(num(12.0), "12");
+    assert_eq!(num(12.5), "12.5");
+    assert_eq!(num(4.8049), "4.8");
+  }
+}
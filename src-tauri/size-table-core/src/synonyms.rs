@@ -0,0 +1,115 @@
+use std::collections::{BTreeMap, HashMap};
+
+/// Spellings designers use for the same measurement, canonical name first
+///
+/// Only the canonical names need glossary entries. Keep the canonical name the one
+/// the glossary already translates.
+const BUILT_IN: &[(&str, &[&str])] = &[
+  ("肩幅", &["肩巾", "肩幅巾"]),
+  ("着丈", &["総丈", "全長"]),
+  ("身幅", &["身巾"]),
+  ("袖丈", &["袖長", "袖の長さ"]),
+  ("裄丈", &["ゆき丈", "裄"]),
+  ("袖口", &["袖口幅", "袖口巾"]),
+  ("バスト", &["バスト周り", "バスト囲", "胸囲"]),
+  (
+    "ウエスト",
+    &["ウエスト周り", "ウエスト囲", "ウエスト回り", "胴囲"],
+  ),
+  ("ヒップ", &["ヒップ周り", "ヒップ囲", "ヒップ回り"]),
+  ("もも周り", &["もも囲", "太もも周り", "わたり幅", "ワタリ"]),
+  ("裾周り", &["裾囲", "裾回り"]),
+  ("裾幅", &["裾巾"]),
+  ("重量", &["重さ"]),
+];
+
+/// Maps every spelling of a measurement name to one canonical Japanese name
+///
+/// Names are compared after trimming and turning full width brackets into ascii ones.
+/// A qualifier in brackets is kept, so "ヒップ周り(ゴム)" becomes "ヒップ(ゴム)".
+#[derive(Debug, Clone)]
+pub struct MeasurementSynonyms {
+  canonical: HashMap<String, String>,
+}
+
+impl Default for MeasurementSynonyms {
+  fn default() -> Self {
+    let mut synonyms = Self {
+      canonical: HashMap::new(),
+    };
+    for (canonical, spellings) in BUILT_IN {
+      synonyms.insert(canonical, spellings.iter().copied());
+    }
+    synonyms
+  }
+}
+
+impl MeasurementSynonyms {
+  /// the built-in table plus `extra`, whose spellings win over built-in ones
+  pub fn with_extra(extra: &BTreeMap<String, Vec<String>>) -> Self {
+    let mut synonyms = Self::default();
+    for (canonical, spellings) in extra {
+      synonyms.insert(canonical, spellings.iter().map(String::as_str));
+    }
+    synonyms
+  }
+
+  fn insert<'a>(&mut self, canonical: &str, spellings: impl Iterator<Item = &'a str>) {
+    let canonical = normalize(canonical);
+    for spelling in spellings {
+      self
+        .canonical
+        .insert(normalize(spelling), canonical.clone());
+    }
+  }
+
+  /// the canonical spelling of `name`, or `name` normalized when it has no synonym
+  pub fn canonical(&self, name: &str) -> String {
+    let name = normalize(name);
+    if let Some(canonical) = self.canonical.get(&name) {
+      return canonical.clone();
+    }
+    match name.find('(') {
+      Some(start) if start > 0 => {
+        let (base, qualifier) = name.split_at(start);
+        match self.canonical.get(base) {
+          Some(canonical) => format!("{canonical}{qualifier}"),
+          None => name,
+        }
+      }
+      _ => name,
+    }
+  }
+}
+
+fn normalize(name: &str) -> String {
+  name
+    .trim()
+    .chars()
+    .map(|c| match c {
+      '（' => '(',
+      '）' => ')',
+      c => c,
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn merge_synonyms() {
+    let synonyms = MeasurementSynonyms::default();
+    assert_eq!(synonyms.canonical("肩巾"), "肩幅");
+    assert_eq!(synonyms.canonical("肩幅"), "肩幅");
+    assert_eq!(synonyms.canonical("総丈"), "着丈");
+    assert_eq!(synonyms.canonical("ヒップ周り（ゴム）"), "ヒップ(ゴム)");
+    assert_eq!(synonyms.canonical(" 股下 "), "股下");
+
+    let extra = BTreeMap::from([("股下".to_string(), vec!["股下丈".to_string()])]);
+    let synonyms = MeasurementSynonyms::with_extra(&extra);
+    assert_eq!(synonyms.canonical("股下丈"), "股下");
+    assert_eq!(synonyms.canonical("肩巾"), "肩幅");
+  }
+}
@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+  measurement::{Amount, MeasurementValue, Unit},
+  models::{ItemMeta, ItemTable},
+};
+
+const CM_PER_INCH: f64 = 2.54;
+/// canonical Japanese names of the measurements whose unitless values are in cm
+const LENGTH_NAMES: &[&str] = &[
+  "肩幅",
+  "着丈",
+  "身幅",
+  "袖丈",
+  "裄丈",
+  "袖口",
+  "バスト",
+  "ウエスト",
+  "ヒップ",
+  "もも周り",
+  "裾周り",
+  "裾幅",
+  "股上",
+  "股下",
+  "首周り",
+  "アームホール",
+];
+
+/// How inches are added to the exported tables
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InchMode {
+  /// centimetres only
+  #[default]
+  Off,
+  /// an extra table next to every table, exported as `size_<code>_inch`
+  SeparateTable,
+  /// "cm / inch" in every length cell
+  Combined,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InchOptions {
+  pub mode: InchMode,
+  /// digits kept after the decimal point of inch values
+  pub decimals: u32,
+}
+
+impl Default for InchOptions {
+  fn default() -> Self {
+    Self {
+      mode: InchMode::Off,
+      decimals: 1,
+    }
+  }
+}
+
+/// The tables to export with inches added as `options` asks
+///
+/// Values in cm or mm, and unitless values of the columns [`ItemTable::lengths`] marks,
+/// are lengths. Anything else, such as weights, counts, values already in inches and text
+/// without a number, is left as it is.
+pub fn with_inches(item_meta: &[ItemMeta], options: &InchOptions) -> Vec<ItemMeta> {
+  match options.mode {
+    InchMode::Off => item_meta.to_vec(),
+    InchMode::SeparateTable => item_meta
+      .iter()
+      .flat_map(|meta| {
+        let inch_meta = ItemMeta {
+          inch: true,
+          table: map_lengths(&meta.table, |cm| {
+            inch_value(cm, options.decimals).to_string()
+          }),
+          ..meta.clone()
+        };
+        [meta.clone(), inch_meta]
+      })
+      .collect(),
+    InchMode::Combined => item_meta
+      .iter()
+      .map(|meta| ItemMeta {
+        table: map_lengths(&meta.table, |cm| {
+          format!("{cm} / {}", inch_value(cm, options.decimals))
+        }),
+        ..meta.clone()
+      })
+      .collect(),
+  }
+}
+
+/// whether the values of the measurement `name`, in canonical Japanese, are lengths
+///
+/// A qualifier in brackets is ignored, so "ヒップ(ゴム)" is a length like "ヒップ".
+pub fn is_length(name: &str) -> bool {
+  let base = name.split('(').next().unwrap_or(name);
+  LENGTH_NAMES.contains(&base)
+}
+
+/// apply `f` to the centimetre value of every length cell, keeping the size column
+fn map_lengths(table: &ItemTable, f: impl Fn(&MeasurementValue) -> String) -> ItemTable {
+  let is_length = |column: usize| table.lengths.get(column).copied().unwrap_or(false);
+  let body = table
+    .body
+    .iter()
+    .map(|row| {
+      row
+        .iter()
+        .enumerate()
+        .map(|(column, cell)| {
+          if column == 0 {
+            return cell.clone();
+          }
+          match to_cm(MeasurementValue::parse(cell), is_length(column)) {
+            Some(cm) => f(&cm),
+            None => cell.clone(),
+          }
+        })
+        .collect()
+    })
+    .collect();
+  ItemTable {
+    head: table.head.clone(),
+    body,
+    lengths: table.lengths.clone(),
+  }
+}
+
+/// the value in cm, if it is a length
+fn to_cm(value: MeasurementValue, in_length_column: bool) -> Option<MeasurementValue> {
+  let amount = value.amount?;
+  let divisor = match value.unit {
+    Some(Unit::Cm) => 1.0,
+    Some(Unit::Mm) => 10.0,
+    None if in_length_column => 1.0,
+    _ => return None,
+  };
+  Some(MeasurementValue {
+    amount: Some(divide(amount, divisor, None)),
+    unit: Some(Unit::Cm),
+    ..value
+  })
+}
+
+fn inch_value(cm: &MeasurementValue, decimals: u32) -> MeasurementValue {
+  MeasurementValue {
+    amount: cm
+      .amount
+      .map(|amount| divide(amount, CM_PER_INCH, Some(decimals))),
+    unit: Some(Unit::Inch),
+    ..cm.clone()
+  }
+}
+
+fn divide(amount: Amount, divisor: f64, decimals: Option<u32>) -> Amount {
+  let convert = |value: f64| {
+    let value = value / divisor;
+    match decimals {
+      Some(decimals) => {
+        let power = 10f64.powi(decimals as i32);
+        (value * power).round() / power
+      }
+      None => value,
+    }
+  };
+  match amount {
+    Amount::Number(number) => Amount::Number(convert(number)),
+    Amount::Range { min, max } => Amount::Range {
+      min: convert(min),
+      max: convert(max),
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::export::ItemFileNames;
+
+  fn meta() -> ItemMeta {
+    ItemMeta {
+      code: "A-1".to_string(),
+      size_code: "01".to_string(),
+      sheet: "Sheet1".to_string(),
+      language: Default::default(),
+      inch: false,
+      table: ItemTable {
+        head: ["尺码", "肩宽", "腰围", "重量", "纽扣", "面料"]
+          .map(String::from)
+          .to_vec(),
+        body: vec![["S", "42.5cm", "62~72", "225", "5", "棉"]
+          .map(String::from)
+          .to_vec()],
+        lengths: vec![false, true, true, false, false, false],
+      },
+    }
+  }
+
+  #[test]
+  fn separate_inch_table() {
+    let options = InchOptions {
+      mode: InchMode::SeparateTable,
+      decimals: 1,
+    };
+    let tables = with_inches(&[meta()], &options);
+    assert_eq!(tables.len(), 2);
+    assert_eq!(tables[1].code, "A-1");
+    assert!(!tables[0].inch && tables[1].inch);
+    let names = ItemFileNames::new(&tables);
+    assert_eq!(names.relative_path(&tables[0], "png"), "A-1/size_A-1.png");
+    assert_eq!(
+      names.relative_path(&tables[1], "png"),
+      "A-1/size_A-1_inch.png"
+    );
+    assert_eq!(
+      tables[1].table.body[0],
+      ["S", "16.7inch", "24.4~28.3inch", "225", "5", "棉"]
+    );
+  }
+
+  #[test]
+  fn combined_cells() {
+    let options = InchOptions {
+      mode: InchMode::Combined,
+      decimals: 0,
+    };
+    let tables = with_inches(&[meta()], &options);
+    assert_eq!(
+      tables[0].table.body[0],
+      [
+        "S",
+        "42.5cm / 17inch",
+        "62~72cm / 24~28inch",
+        "225",
+        "5",
+        "棉"
+      ]
+    );
+  }
+
+  #[test]
+  fn length_names() {
+    assert!(is_length("肩幅"));
+    assert!(is_length("ヒップ(ゴム)"));
+    assert!(!is_length("重量"));
+    assert!(!is_length("ボタン数"));
+  }
+}
@@ -0,0 +1,180 @@
+use std::{
+  collections::BTreeMap,
+  fs,
+  io::ErrorKind,
+  path::{Path, PathBuf},
+};
+
+use phdb_translate::{TargetLanguage, TranslateClient};
+use serde::Serialize;
+
+use crate::{config::config_dir, Error, Result};
+
+/// Glossary entries kept by the user in `glossary.csv` next to `config.json`
+///
+/// They override and extend the glossary built into `phdb-translate`, so a missing
+/// term can be added without a rebuild. The file has the same `ja,zh` layout as the
+/// built-in glossary. Every other language has its own file, such as `glossary.ko.csv`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserGlossary(pub BTreeMap<String, String>);
+
+/// A user entry as listed in the app
+#[derive(Debug, Clone, Serialize)]
+pub struct GlossaryEntry {
+  pub source: String,
+  pub target: String,
+  /// the built-in translation this entry overrides, if any
+  pub builtin: Option<String>,
+}
+
+/// `<config dir>/Size Table Generator/glossary.csv`, or `glossary.<code>.csv` for
+/// another language than Simplified Chinese
+pub fn user_glossary_path(language: TargetLanguage) -> Result<PathBuf> {
+  let file_name = match language {
+    TargetLanguage::ZhHans => "glossary.csv".to_string(),
+    other => format!("glossary.{other}.csv"),
+  };
+  Ok(config_dir()?.join(file_name))
+}
+
+impl UserGlossary {
+  /// read the glossary file of `language`, empty when there is none
+  pub fn load(language: TargetLanguage) -> Result<Self> {
+    Self::load_from(user_glossary_path(language)?)
+  }
+
+  /// read the glossary file of every language and hand each to `client`
+  pub fn apply_all(client: &mut TranslateClient) -> Result<()> {
+    for language in TargetLanguage::ALL {
+      Self::load(language)?.apply(client, language);
+    }
+    Ok(())
+  }
+
+  pub fn load_from(path: impl AsRef<Path>) -> Result<Self> {
+    let text = match fs::read_to_string(path) {
+      Ok(text) => text,
+      Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+      Err(e) => return Err(e.into()),
+    };
+    let mut entries = BTreeMap::new();
+    for record in csv::Reader::from_reader(text.as_bytes()).records() {
+      let record = record.map_err(Error::CsvRead)?;
+      if let (Some(source), Some(target)) = (record.get(0), record.get(1)) {
+        entries.insert(source.trim().to_string(), target.trim().to_string());
+      }
+    }
+    Ok(Self(entries))
+  }
+
+  pub fn save(&self, language: TargetLanguage) -> Result<()> {
+    self.save_to(user_glossary_path(language)?, language)
+  }
+
+  pub fn save_to(&self, path: impl AsRef<Path>, language: TargetLanguage) -> Result<()> {
+    let path = path.as_ref();
+    if let Some(dir) = path.parent() {
+      fs::create_dir_all(dir)?;
+    }
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record(["ja", language.code()])?;
+    for (source, target) in &self.0 {
+      writer.write_record([source, target])?;
+    }
+    writer.flush()?;
+    Ok(())
+  }
+
+  pub fn add(&mut self, source: &str, target: &str) -> Result<()> {
+    let (source, target) = entry(source, target)?;
+    if self.0.contains_key(&source) {
+      return Err(Error::GlossaryEntryExists(source));
+    }
+    self.0.insert(source, target);
+    Ok(())
+  }
+
+  /// change the translation of `source`, and its spelling when `new_source` differs
+  pub fn edit(&mut self, source: &str, new_source: &str, target: &str) -> Result<()> {
+    let (new_source, target) = entry(new_source, target)?;
+    if !self.0.contains_key(source) {
+      return Err(Error::GlossaryEntryNotFound(source.to_string()));
+    }
+    if new_source != source && self.0.contains_key(&new_source) {
+      return Err(Error::GlossaryEntryExists(new_source));
+    }
+    self.0.remove(source);
+    self.0.insert(new_source, target);
+    Ok(())
+  }
+
+  pub fn remove(&mut self, source: &str) -> Result<()> {
+    self
+      .0
+      .remove(source)
+      .map(|_| ())
+      .ok_or_else(|| Error::GlossaryEntryNotFound(source.to_string()))
+  }
+
+  /// use these entries for every following translation of `client` into `language`
+  pub fn apply(&self, client: &mut TranslateClient, language: TargetLanguage) {
+    client.set_user_glossary(language, self.0.clone().into_iter().collect());
+  }
+
+  pub fn entries(&self, client: &TranslateClient, language: TargetLanguage) -> Vec<GlossaryEntry> {
+    self
+      .0
+      .iter()
+      .map(|(source, target)| GlossaryEntry {
+        source: source.clone(),
+        target: target.clone(),
+        builtin: client
+          .builtin_translation(source, language)
+          .map(String::from),
+      })
+      .collect()
+  }
+}
+
+fn entry(source: &str, target: &str) -> Result<(String, String)> {
+  let (source, target) = (source.trim(), target.trim());
+  if source.is_empty() || target.is_empty() {
+    return Err(Error::EmptyGlossaryEntry);
+  }
+  Ok((source.to_string(), target.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn edit_and_round_trip() {
+    let mut glossary = UserGlossary::default();
+    glossary.add("肩巾", "肩宽").unwrap();
+    glossary.add(" 股下 ", "内长").unwrap();
+    assert!(matches!(
+      glossary.add("肩巾", "肩宽"),
+      Err(Error::GlossaryEntryExists(_))
+    ));
+    assert!(matches!(
+      glossary.add("袖丈", " "),
+      Err(Error::EmptyGlossaryEntry)
+    ));
+    glossary.edit("股下", "股下", "裤内长").unwrap();
+    glossary.edit("肩巾", "肩幅", "肩宽").unwrap();
+    assert!(matches!(
+      glossary.remove("肩巾"),
+      Err(Error::GlossaryEntryNotFound(_))
+    ));
+
+    let path = std::env::temp_dir().join(format!("stg-glossary-{}.csv", std::process::id()));
+    glossary.save_to(&path, TargetLanguage::Ko).unwrap();
+    assert!(fs::read_to_string(&path).unwrap().starts_with("ja,ko\n"));
+    let loaded = UserGlossary::load_from(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, glossary);
+    assert_eq!(loaded.0["股下"], "裤内长");
+    assert_eq!(loaded.0["肩幅"], "肩宽");
+  }
+}
@@ -0,0 +1,199 @@
+use std::collections::{BTreeMap, HashMap};
+
+use phdb_translate::TargetLanguage;
+use serde::Serialize;
+
+use crate::{
+  columns::{column_name, Column},
+  Error,
+};
+
+/// What was wrong with a cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueKind {
+  InvalidItemCode,
+  InvalidSizeCode,
+  InvalidSizeText,
+  EmptySizeText,
+}
+
+/// One cell that could not be read
+#[derive(Debug, Clone, Serialize)]
+pub struct RowIssue {
+  pub sheet: String,
+  /// 1 based, as shown by the spreadsheet app
+  pub row: usize,
+  pub column: Column,
+  /// letters of the column, such as "C"
+  pub column_name: String,
+  /// the cell as it was written
+  pub value: String,
+  pub kind: IssueKind,
+  /// the same message the error would have shown
+  pub message: String,
+}
+
+impl RowIssue {
+  /// `column_index` is 0 based and counts from the first column of the sheet
+  pub fn new(
+    sheet: &str,
+    row: usize,
+    column: Column,
+    column_index: usize,
+    value: impl Into<String>,
+    error: Error,
+  ) -> Self {
+    let kind = match (column, &error) {
+      (Column::ItemCode, _) => IssueKind::InvalidItemCode,
+      (Column::SizeCode, _) => IssueKind::InvalidSizeCode,
+      (Column::SizeText, Error::EmptySizeText) => IssueKind::EmptySizeText,
+      (Column::SizeText, _) => IssueKind::InvalidSizeText,
+    };
+    Self {
+      sheet: sheet.to_string(),
+      row,
+      column,
+      column_name: column_name(column_index),
+      value: value.into(),
+      kind,
+      message: String::from(error),
+    }
+  }
+}
+
+/// Rows sharing one (品番, SZ) pair
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateRows {
+  pub sheet: String,
+  pub item_code: String,
+  pub size_code: String,
+  /// 1 based, in sheet order
+  pub rows: Vec<usize>,
+  /// the [採寸] cell of each row
+  pub values: Vec<String>,
+  /// false when every row has the same [採寸], which is harmless
+  pub conflicting: bool,
+  /// the row the table was built from
+  pub kept_row: usize,
+}
+
+/// Measurements some sizes of an item list and others do not
+#[derive(Debug, Clone, Serialize)]
+pub struct MeasurementMismatch {
+  pub sheet: String,
+  pub item_code: String,
+  pub missing: Vec<MissingMeasurements>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MissingMeasurements {
+  pub size_code: String,
+  /// measurements listed by other sizes of the item, but not by this one
+  pub names: Vec<String>,
+}
+
+/// A measurement name the glossary could not translate
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UntranslatedTerm {
+  pub term: String,
+  /// the language the glossary has no entry of the term for
+  pub language: TargetLanguage,
+  /// item codes whose tables show the term, in processing order
+  pub item_codes: Vec<String>,
+  /// the translation shown in the tables when the term was machine translated,
+  /// `None` when it was left in Japanese
+  pub machine_translation: Option<String>,
+}
+
+/// one entry per term, sorted by term, from (term, item code) pairs
+pub fn untranslated_terms(
+  misses: impl IntoIterator<Item = (String, String)>,
+  machine_translations: &HashMap<String, String>,
+  language: TargetLanguage,
+) -> Vec<UntranslatedTerm> {
+  let mut terms: BTreeMap<String, Vec<String>> = BTreeMap::new();
+  for (term, item_code) in misses {
+    let item_codes = terms.entry(term).or_default();
+    if !item_codes.contains(&item_code) {
+      item_codes.push(item_code);
+    }
+  }
+  terms
+    .into_iter()
+    .map(|(term, item_codes)| UntranslatedTerm {
+      machine_translation: machine_translations.get(&term).cloned(),
+      term,
+      language,
+      item_codes,
+    })
+    .collect()
+}
+
+/// Every problem found while reading the rows of a workbook
+///
+/// Items with a bad row are left out of the generated tables, so a table never
+/// silently misses a size.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationReport {
+  pub issues: Vec<RowIssue>,
+  pub duplicates: Vec<DuplicateRows>,
+  /// the tables are still generated, with the missing cells marked
+  pub measurement_mismatches: Vec<MeasurementMismatch>,
+}
+
+impl ValidationReport {
+  pub fn is_empty(&self) -> bool {
+    self.issues.is_empty() && self.duplicates.is_empty() && self.measurement_mismatches.is_empty()
+  }
+
+  pub fn push(&mut self, issue: RowIssue) {
+    self.issues.push(issue);
+  }
+
+  /// duplicates whose rows disagree, all but the kept row were dropped
+  pub fn conflicts(&self) -> impl Iterator<Item = &DuplicateRows> {
+    self
+      .duplicates
+      .iter()
+      .filter(|duplicate| duplicate.conflicting)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn issue_of_size_text() {
+    let error = "肩幅40".parse::<crate::SizeDetails>().err().unwrap();
+    let issue = RowIssue::new("Sheet1", 3, Column::SizeText, 3, "肩幅40", error);
+    assert_eq!(issue.kind, IssueKind::InvalidSizeText);
+    assert_eq!(issue.column_name, "D");
+    assert_eq!(issue.message, "採寸格式错误:肩幅40");
+
+    let error = "".parse::<crate::SizeDetails>().err().unwrap();
+    let issue = RowIssue::new("Sheet1", 4, Column::SizeText, 3, "", error);
+    assert_eq!(issue.kind, IssueKind::EmptySizeText);
+  }
+
+  #[test]
+  fn deduplicate_untranslated_terms() {
+    let misses = [
+      ("袖幅", "A-1"),
+      ("袖幅", "A-1"),
+      ("肩線", "B-2"),
+      ("袖幅", "B-2"),
+    ]
+    .map(|(term, code)| (term.to_string(), code.to_string()));
+    let machine = HashMap::from([("袖幅".to_string(), "袖宽".to_string())]);
+    let terms = untranslated_terms(misses, &machine, TargetLanguage::Ko);
+    assert_eq!(terms.len(), 2);
+    assert_eq!(terms[0].language, TargetLanguage::Ko);
+    assert_eq!(terms[0].term, "肩線");
+    assert_eq!(terms[0].machine_translation, None);
+    assert_eq!(terms[1].term, "袖幅");
+    assert_eq!(terms[1].item_codes, ["A-1", "B-2"]);
+    assert_eq!(terms[1].machine_translation.as_deref(), Some("袖宽"));
+  }
+}
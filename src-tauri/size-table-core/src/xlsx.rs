@@ -0,0 +1,205 @@
+use std::{collections::HashSet, path::Path};
+
+use rust_xlsxwriter::{ColNum, Format, RowNum, Workbook, Worksheet, XlsxError};
+
+use crate::{
+  export::ItemFileNames,
+  labels::{item_code_label, table_title},
+  models::ItemMeta,
+  Result,
+};
+
+/// How the generated tables are laid out in the workbook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XlsxLayout {
+  /// one worksheet per table, named like the exported files
+  SheetPerItem,
+  /// every table on one worksheet, with a leading [品番] column
+  SingleSheet,
+}
+
+/// excel rejects longer sheet names
+const MAX_SHEET_NAME_LEN: usize = 31;
+
+pub fn write_xlsx(
+  item_meta: &[ItemMeta],
+  path: impl AsRef<Path>,
+  layout: XlsxLayout,
+) -> Result<()> {
+  let mut workbook = Workbook::new();
+  let head_format = Format::new().set_bold();
+  match layout {
+    XlsxLayout::SheetPerItem => {
+      let names = ItemFileNames::new(item_meta);
+      let mut used_names = HashSet::new();
+      for meta in item_meta {
+        let worksheet = workbook.add_worksheet();
+        worksheet.set_name(unique_sheet_name(&names.name(meta), &mut used_names))?;
+        write_rows(worksheet, 0, 0, meta, &head_format)?;
+        worksheet.set_freeze_panes(1, 0)?;
+      }
+    }
+    XlsxLayout::SingleSheet => {
+      let worksheet = workbook.add_worksheet();
+      // named after the first table, as excel does not allow "/" to join several titles
+      let language = item_meta.first().map(|meta| meta.language);
+      worksheet.set_name(table_title(language.unwrap_or_default()))?;
+      let mut row: RowNum = 0;
+      for meta in item_meta {
+        // every item keeps its own header row, because the measurements differ
+        let label = item_code_label(meta.language);
+        worksheet.write_string_with_format(row, 0, label, &head_format)?;
+        for body_row in 1..=meta.table.body.len() as RowNum {
+          worksheet.write_string(row + body_row, 0, &meta.code)?;
+        }
+        row = write_rows(worksheet, row, 1, meta, &head_format)? + 1;
+      }
+    }
+  }
+  workbook.save(path)?;
+  Ok(())
+}
+
+/// write the head and body of the table from (`row`, `col`), returning the next empty row
+fn write_rows(
+  worksheet: &mut Worksheet,
+  row: RowNum,
+  col: ColNum,
+  meta: &ItemMeta,
+  head_format: &Format,
+) -> std::result::Result<RowNum, XlsxError> {
+  for (i, head) in meta.table.head.iter().enumerate() {
+    worksheet.write_string_with_format(row, col + i as ColNum, head, head_format)?;
+  }
+  let mut current = row + 1;
+  for body_row in &meta.table.body {
+    for (i, cell) in body_row.iter().enumerate() {
+      worksheet.write_string(current, col + i as ColNum, cell)?;
+    }
+    current += 1;
+  }
+  Ok(current)
+}
+
+fn sheet_name(code: &str) -> String {
+  code
+    .chars()
+    .map(|c| match c {
+      '[' | ']' | ':' | '*' | '?' | '/' | '\\' => '_',
+      c => c,
+    })
+    .take(MAX_SHEET_NAME_LEN)
+    .collect()
+}
+
+/// [`sheet_name`] of `code`, numbered like "A-1 (2)" when it is already in `used_names`
+///
+/// excel compares sheet names case-insensitively, and codes that only differ after the
+/// 31st character are the same once truncated.
+fn unique_sheet_name(code: &str, used_names: &mut HashSet<String>) -> String {
+  let base = sheet_name(code);
+  let mut name = base.clone();
+  let mut n = 2;
+  while !used_names.insert(name.to_lowercase()) {
+    let suffix = format!(" ({n})");
+    let keep = MAX_SHEET_NAME_LEN - suffix.chars().count();
+    name = base.chars().take(keep).collect::<String>() + &suffix;
+    n += 1;
+  }
+  name
+}
+
+#[cfg(test)]
+mod tests {
+  use std::fs;
+
+  use calamine::DataType;
+
+  use super::*;
+  use crate::{input::InputWorkbook, models::ItemTable};
+
+  fn meta(code: &str, sheet: &str) -> ItemMeta {
+    ItemMeta {
+      code: code.to_string(),
+      size_code: "01".to_string(),
+      sheet: sheet.to_string(),
+      language: Default::default(),
+      inch: false,
+      table: ItemTable {
+        head: vec!["尺码".to_string(), "肩宽".to_string()],
+        body: vec![
+          vec!["S".to_string(), "42".to_string()],
+          vec!["M".to_string(), "44".to_string()],
+        ],
+        lengths: Vec::new(),
+      },
+    }
+  }
+
+  /// write `item_meta` and read the workbook back
+  fn round_trip(item_meta: &[ItemMeta], layout: XlsxLayout) -> InputWorkbook {
+    let path =
+      std::env::temp_dir().join(format!("stg-xlsx-{}-{:?}.xlsx", std::process::id(), layout));
+    write_xlsx(item_meta, &path, layout).unwrap();
+    let workbook = InputWorkbook::open(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    workbook
+  }
+
+  fn cell(range: &calamine::Range<DataType>, row: u32, col: u32) -> String {
+    range
+      .get_value((row, col))
+      .map(|value| value.to_string())
+      .unwrap_or_default()
+  }
+
+  #[test]
+  fn sheet_per_item() {
+    let long_code = "X".repeat(MAX_SHEET_NAME_LEN);
+    let item_meta = [
+      meta("A/1", "Sheet1"),
+      meta("A/1", "Sheet2"),
+      meta(&format!("{long_code}-1"), "Sheet1"),
+      meta(&format!("{long_code}-2"), "Sheet1"),
+      meta("b-1", "Sheet1"),
+      meta("B-1", "Sheet1"),
+    ];
+    let mut workbook = round_trip(&item_meta, XlsxLayout::SheetPerItem);
+    assert_eq!(
+      workbook.sheet_names(),
+      [
+        "A_1_Sheet1".to_string(),
+        "A_1_Sheet2".to_string(),
+        long_code.clone(),
+        format!("{} (2)", &long_code[..MAX_SHEET_NAME_LEN - 4]),
+        "b-1".to_string(),
+        "B-1 (2)".to_string(),
+      ]
+    );
+    let range = workbook.worksheet_range_at(1).unwrap().unwrap();
+    assert_eq!(cell(&range, 0, 0), "尺码");
+    assert_eq!(cell(&range, 0, 1), "肩宽");
+    assert_eq!(cell(&range, 2, 1), "44");
+  }
+
+  #[test]
+  fn single_sheet() {
+    let item_meta = [meta("A-1", "Sheet1"), meta("B-2", "Sheet1")];
+    let mut workbook = round_trip(&item_meta, XlsxLayout::SingleSheet);
+    assert_eq!(workbook.sheet_names(), ["尺码表".to_string()]);
+    let range = workbook.worksheet_range_at(0).unwrap().unwrap();
+    // a blank row separates the tables
+    for (row, expected) in [
+      (0, item_code_label(Default::default())),
+      (1, "A-1"),
+      (2, "A-1"),
+      (3, ""),
+      (4, item_code_label(Default::default())),
+      (5, "B-2"),
+    ] {
+      assert_eq!(cell(&range, row, 0), expected);
+    }
+    assert_eq!(cell(&range, 4, 1), "尺码");
+    assert_eq!(cell(&range, 5, 2), "42");
+  }
+}
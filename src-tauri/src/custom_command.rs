@@ -1,37 +1,28 @@
-use std::{str::FromStr, sync::Arc};
+use std::sync::Arc;
 
-use calamine::{open_workbook, DataType, Reader, Xlsx};
-use itertools::Itertools;
-use melrose_types::{ItemCode, SizeCode};
-use phdb_translate::TranslateClient;
+use phdb_translate::{CacheEntry, TargetLanguage, TranslateClient};
 use serde::Serialize;
+use size_table_core::{
+  export::{export_item_metas, ExportFormat},
+  list_sheets, load_config, process_workbook,
+  project::{glossary_version, CellEdit, ProjectFile},
+  units::{with_inches, InchMode},
+  GlossaryEntry, ItemMeta, SheetInfo, SheetSelection, UntranslatedTerm, UserGlossary,
+  ValidationReport,
+};
 use tauri::async_runtime::Mutex;
 use tauri::Emitter;
 
-use crate::{Error, Result};
+use crate::Error;
 
 #[derive(Serialize)]
 pub struct ProcessResponse {
   item_meta: Vec<ItemMeta>,
-}
-
-struct ItemInfo {
-  item_code: ItemCode,
-  size_code: SizeCode,
-  size_text: SizeDetails,
-}
-
-#[derive(Debug, Serialize)]
-struct ItemTable {
-  head: Vec<String>,
-  body: Vec<Vec<String>>,
-}
-
-#[derive(Debug, Serialize)]
-struct ItemMeta {
-  code: String,
-  size_code: String,
-  table: ItemTable,
+  /// rows that could not be read; their items are not in `item_meta`
+  report: ValidationReport,
+  /// measurement names the glossary has no entry for, left in Japanese unless they
+  /// were machine translated
+  untranslated: Vec<UntranslatedTerm>,
 }
 
 #[derive(Serialize, Clone)]
@@ -39,103 +30,13 @@ struct ProcessingStatePayload {
   state: String,
 }
 
-#[derive(Clone)]
-struct SizeDetail {
-  name: String,
-  value: String,
-}
-
-impl FromStr for SizeDetail {
-  type Err = Error;
-
-  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-    if s.is_empty() {
-      return Err(Error::EmptySizeText);
-    }
-
-    let escaped = escape_colon_whitespace(s);
-    println!("escaped: {}", escaped);
-    // should contain ':' and only one ':'
-    if !escaped.contains(':') || escaped.matches(':').count() != 1 {
-      println!("error line: {}", escaped);
-      return Err(Error::InvalidSizeText {
-        error_line: escaped,
-      });
-    }
-
-    let name_value_pair = escaped.split(':').collect_vec();
-    if name_value_pair.len() != 2 {
-      println!("error line: {:?}", name_value_pair);
-      return Err(Error::InvalidSizeText {
-        error_line: escaped,
-      });
-    }
-
-    if name_value_pair[0].is_empty() || name_value_pair[1].is_empty() {
-      println!("error line: {:?}", name_value_pair);
-      return Err(Error::InvalidSizeText {
-        error_line: escaped,
-      });
-    }
-
-    Ok(Self {
-      name: name_value_pair[0].to_string(),
-      value: name_value_pair[1].to_string(),
-    })
-  }
-}
-
-/// A Contain the multi name value pairs of size detail separated by whitespace
-/// For instance: 肩宽:42.5cm 袖丈:62cm 胸囲:104cm 裾囲:104cm
-#[derive(Clone)]
-struct SizeDetails(Vec<SizeDetail>);
-
-impl FromStr for SizeDetails {
-  type Err = Error;
-
-  fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-    if s.is_empty() {
-      return Err(Error::EmptySizeText);
-    }
-
-    let splitted = s.split_whitespace().collect_vec();
-    if splitted.is_empty() {
-      return Err(Error::EmptySizeText);
-    }
-
-    let size_details = splitted
-      .iter()
-      .map(|s| SizeDetail::from_str(s))
-      .collect::<std::result::Result<Vec<_>, Error>>()?;
-
-    Ok(Self(size_details))
-  }
-}
-
-impl SizeDetails {
-  fn names(&self) -> Vec<String> {
-    self.0.iter().map(|sd| sd.name.to_owned()).collect()
-  }
-
-  fn values(&self) -> Vec<String> {
-    self.0.iter().map(|sd| sd.value.to_owned()).collect()
-  }
-
-  /// translate the name field of all size_detail to chinese
-  async fn translate_to_zh(self, translate_client: &mut TranslateClient) -> Result<Self> {
-    let translated = translate_client.translate_local(&self.names())?;
-    let mut cloned_self = self.clone();
-    for (i, sd) in cloned_self.0.iter_mut().enumerate() {
-      sd.name = translated[i].to_owned();
-    }
-    Ok(cloned_self)
-  }
-}
-
 #[tauri::command]
 pub async fn process_excel_file(
   window: tauri::Window,
   excel_path: String,
+  sheets: Option<SheetSelection>,
+  machine_translation: Option<bool>,
+  languages: Option<Vec<TargetLanguage>>,
   client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
 ) -> std::result::Result<ProcessResponse, String> {
   println!("command invoked");
@@ -148,135 +49,221 @@ pub async fn process_excel_file(
     )
     .map_err(Error::Tauri)?;
 
-  let mut excel_file: Xlsx<_> = open_workbook(excel_path).map_err(|_| Error::ExcelRead)?;
-  let items_code_sheet = excel_file
-    .worksheet_range_at(0)
-    .ok_or(Error::EmptyFile)?
-    .map_err(|_| Error::EmptyFile)?;
-  let (item_code_idx, size_code_idx, size_text_idx) = items_code_sheet
-    .rows()
-    .next()
-    .ok_or(Error::EmptyFile)?
-    .iter()
-    .enumerate()
-    .filter_map(|(i, cell)| check_column(i, cell.to_string()).ok())
-    .collect_tuple()
-    .ok_or(Error::InvalidSheetFormat)?;
-  // TODO)) 必要なフィールドに空欄がある場合、無視にする？
-  // いらない行に消し忘れがあると、気づかない
-  let item_code_size_unique = items_code_sheet
-    .rows()
-    .skip(1)
-    .unique_by(|row| {
-      (
-        row[item_code_idx].to_string(),
-        row[size_code_idx].to_string(),
-      )
-    })
-    .sorted_by(|a, b| {
-      a[item_code_idx]
-        .to_string()
-        .cmp(&b[item_code_idx].to_string())
-    })
-    .collect_vec();
-  let mut item_code_isolated_rows: Vec<Vec<&[DataType]>> = Vec::new();
-  let mut changed_item_code = item_code_size_unique[0][item_code_idx].to_string();
-  let mut rows_vec = Vec::new();
-  let rows_len = item_code_size_unique.len();
-  for (i, row) in item_code_size_unique.into_iter().enumerate() {
-    if row[item_code_idx].to_string() == changed_item_code {
-      rows_vec.push(row);
-      continue;
-    }
-    item_code_isolated_rows.push(rows_vec);
-    changed_item_code = row[item_code_idx].to_string();
-    rows_vec = vec![row];
-    if i == rows_len - 1 {
-      item_code_isolated_rows.push(rows_vec);
-      break;
-    }
+  // read on every run, so edits to config.json apply without a restart
+  let config = load_config().map_err(Error::Core)?;
+  let mut options = config.process_options();
+  options.sheets = sheets.unwrap_or_default();
+  if let Some(machine_translation) = machine_translation {
+    options.machine_translation = machine_translation;
+  }
+  if let Some(languages) = languages {
+    options.languages = languages;
   }
-  window
-    .emit(
-      "update-state",
-      ProcessingStatePayload {
-        state: "translating".into(),
-      },
-    )
-    .unwrap();
   let mut local_client = client.lock().await;
-  let mut item_code_size_data = Vec::new();
-  for item_code_isolated_row in item_code_isolated_rows {
-    let mut item_infos = Vec::new();
-    for row in item_code_isolated_row {
-      println!("row: {:?}", row);
-      println!("item_code: {:?}", &row[item_code_idx].to_string());
-      println!("size_code: {:?}", &row[size_code_idx].to_string());
-      let item_code = row[item_code_idx]
-        .to_string()
-        .replace(" ", "_")
-        .parse::<ItemCode>()
-        .map_err(|e| Error::MelroseType(melrose_types::error::Error::from(e)))?;
-      let size_code = row[size_code_idx]
-        .to_string()
-        .parse::<SizeCode>()
-        .map_err(|e| Error::MelroseType(melrose_types::error::Error::from(e)))?;
-      let size_text: SizeDetails = row[size_text_idx].to_string().parse()?;
-      let size_text_zh = size_text.translate_to_zh(&mut local_client).await?;
-      item_infos.push(ItemInfo {
-        item_code,
-        size_code,
-        size_text: size_text_zh,
-      });
-    }
-    item_code_size_data.push(item_infos);
+  // the glossary files too, in case they were edited by hand
+  UserGlossary::apply_all(&mut local_client).map_err(Error::Core)?;
+  if options.machine_translation {
+    local_client.set_translator(config.translator().map_err(Error::Core)?);
   }
+  let output = process_workbook(excel_path, &mut local_client, &options, |state| {
+    window
+      .emit(
+        "update-state",
+        ProcessingStatePayload {
+          state: state.into(),
+        },
+      )
+      .unwrap();
+  })
+  .await
+  .map_err(Error::Core)?;
+  println!("item_meta: {:?}", output.item_meta);
+  Ok(ProcessResponse {
+    item_meta: output.item_meta,
+    report: output.report,
+    untranslated: output.untranslated,
+  })
+}
 
-  window
-    .emit(
-      "update-state",
-      ProcessingStatePayload {
-        state: "processing file".into(),
-      },
-    )
-    .unwrap();
-  let mut item_meta = Vec::new();
-  for item_infos in item_code_size_data {
-    let mut table_head = item_infos[0].size_text.names();
-    table_head.insert(0, String::from("尺码"));
-    let table_body = item_infos
-      .iter()
-      .map(|item_info| {
-        let mut size_row_raw = item_info.size_text.values();
-        size_row_raw.insert(0, item_info.size_code.to_roman_numeral());
-        size_row_raw
-      })
-      .collect::<Vec<_>>();
-    let table = ItemTable {
-      head: table_head,
-      body: table_body,
-    };
-    item_meta.push(ItemMeta {
-      code: item_infos[0].item_code.to_string(),
-      size_code: item_infos[0].size_code.to_string(),
-      table,
-    });
+/// worksheet names of the workbook with their row counts, to choose what to process
+#[tauri::command]
+pub async fn list_excel_sheets(excel_path: String) -> std::result::Result<Vec<SheetInfo>, String> {
+  Ok(list_sheets(excel_path).map_err(Error::Core)?)
+}
+
+/// write the size tables to `<save_dir>/<code>/size_<code>.<ext>`
+///
+/// `format` is one of the formats accepted by `ExportFormat`, such as "jpg" or "svg".
+/// `inch_mode` overrides the inch output of config.json for this export
+#[tauri::command]
+pub async fn export_size_tables(
+  item_meta: Vec<ItemMeta>,
+  save_dir: String,
+  format: String,
+  inch_mode: Option<InchMode>,
+) -> std::result::Result<Vec<String>, String> {
+  let format: ExportFormat = format.parse().map_err(Error::Core)?;
+  let mut inch = load_config().map_err(Error::Core)?.inch;
+  if let Some(mode) = inch_mode {
+    inch.mode = mode;
   }
-  println!("item_meta: {:?}", item_meta);
-  Ok(ProcessResponse { item_meta })
+  let item_meta = with_inches(&item_meta, &inch);
+  let written = export_item_metas(&item_meta, save_dir, format).map_err(Error::Core)?;
+  Ok(
+    written
+      .into_iter()
+      .map(|path| path.display().to_string())
+      .collect(),
+  )
+}
+
+#[derive(Serialize)]
+pub struct ProjectResponse {
+  project: ProjectFile,
+  /// the tables with the manual edits applied
+  item_meta: Vec<ItemMeta>,
+  source_changed: bool,
+  glossary_changed: bool,
+}
+
+/// save the processing result with its source file and manual edits
+#[tauri::command]
+pub async fn save_project(
+  project_path: String,
+  excel_path: String,
+  item_meta: Vec<ItemMeta>,
+  edits: Vec<CellEdit>,
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<(), String> {
+  let glossary_version = glossary_version(&*client.lock().await, &item_meta);
+  let project =
+    ProjectFile::new(excel_path, glossary_version, item_meta, edits).map_err(Error::Core)?;
+  project.save(project_path).map_err(Error::Core)?;
+  Ok(())
+}
+
+/// reopen a saved processing result without reading the workbook again
+#[tauri::command]
+pub async fn load_project(
+  project_path: String,
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<ProjectResponse, String> {
+  let project = ProjectFile::load(project_path).map_err(Error::Core)?;
+  let glossary_changed =
+    glossary_version(&*client.lock().await, &project.item_meta) != project.glossary_version;
+  Ok(ProjectResponse {
+    item_meta: project.edited_item_meta(),
+    source_changed: project.source_changed(),
+    glossary_changed,
+    project,
+  })
+}
+
+/// entries of the user glossary, with the built-in translations they override
+///
+/// every glossary command works on the glossary of `language`, Simplified Chinese when
+/// it is not given
+#[tauri::command]
+pub async fn list_glossary_entries(
+  language: Option<TargetLanguage>,
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<Vec<GlossaryEntry>, String> {
+  let language = language.unwrap_or_default();
+  let user_glossary = UserGlossary::load(language).map_err(Error::Core)?;
+  Ok(user_glossary.entries(&*client.lock().await, language))
 }
 
-fn check_column(i: usize, s: impl AsRef<str>) -> Result<usize> {
-  match s.as_ref().trim() {
-    "品番" | "SZ" | "採寸" => Ok(i),
-    _ => Err(Error::InvalidSheetFormat),
+#[tauri::command]
+pub async fn add_glossary_entry(
+  source: String,
+  target: String,
+  language: Option<TargetLanguage>,
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<Vec<GlossaryEntry>, String> {
+  update_user_glossary(&client, language, |user_glossary| {
+    user_glossary.add(&source, &target)
+  })
+  .await
+}
+
+/// `new_source` renames the entry, pass `source` again to only change the translation
+#[tauri::command]
+pub async fn edit_glossary_entry(
+  source: String,
+  new_source: String,
+  target: String,
+  language: Option<TargetLanguage>,
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<Vec<GlossaryEntry>, String> {
+  update_user_glossary(&client, language, |user_glossary| {
+    user_glossary.edit(&source, &new_source, &target)
+  })
+  .await
+}
+
+#[tauri::command]
+pub async fn delete_glossary_entry(
+  source: String,
+  language: Option<TargetLanguage>,
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<Vec<GlossaryEntry>, String> {
+  update_user_glossary(&client, language, |user_glossary| {
+    user_glossary.remove(&source)
+  })
+  .await
+}
+
+/// change the glossary file of `language` and hand it to the client, so the next run
+/// already uses it
+async fn update_user_glossary(
+  client: &Mutex<TranslateClient>,
+  language: Option<TargetLanguage>,
+  update: impl FnOnce(&mut UserGlossary) -> size_table_core::Result<()>,
+) -> std::result::Result<Vec<GlossaryEntry>, String> {
+  let language = language.unwrap_or_default();
+  let mut user_glossary = UserGlossary::load(language).map_err(Error::Core)?;
+  update(&mut user_glossary).map_err(Error::Core)?;
+  user_glossary.save(language).map_err(Error::Core)?;
+  let mut local_client = client.lock().await;
+  user_glossary.apply(&mut local_client, language);
+  Ok(user_glossary.entries(&local_client, language))
+}
+
+/// machine translations kept in the translation cache
+#[tauri::command]
+pub async fn list_translation_cache(
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<Vec<CacheEntry>, String> {
+  Ok(
+    client
+      .lock()
+      .await
+      .cache()
+      .map(|cache| cache.entries())
+      .unwrap_or_default(),
+  )
+}
+
+/// write the translation cache to `export_path` as CSV
+#[tauri::command]
+pub async fn export_translation_cache(
+  export_path: String,
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<(), String> {
+  if let Some(cache) = client.lock().await.cache() {
+    cache
+      .export_csv(export_path)
+      .map_err(|e| Error::Core(e.into()))?;
   }
+  Ok(())
 }
 
-#[inline]
-fn escape_colon_whitespace(s: impl AsRef<str>) -> String {
-  s.as_ref()
-    .replace('：', ":")
-    .replace(": ", ":")
-    .replace('　', " ")
+#[tauri::command]
+pub async fn clear_translation_cache(
+  client: tauri::State<'_, Arc<Mutex<TranslateClient>>>,
+) -> std::result::Result<(), String> {
+  if let Some(cache) = client.lock().await.cache_mut() {
+    cache.clear().map_err(|e| Error::Core(e.into()))?;
+  }
+  Ok(())
 }
@@ -6,39 +6,52 @@
 mod custom_command;
 
 use phdb_translate::TranslateClient;
+use size_table_core::{open_translation_cache, UserGlossary};
 use std::sync::Arc;
 use tauri::async_runtime::Mutex;
 
-use custom_command::process_excel_file;
-
-pub const APP_IDENTIFIER: &str = "Size Table Generator";
+use custom_command::{
+  add_glossary_entry, clear_translation_cache, delete_glossary_entry, edit_glossary_entry,
+  export_size_tables, export_translation_cache, list_excel_sheets, list_glossary_entries,
+  list_translation_cache, load_project, process_excel_file, save_project,
+};
 
 fn main() {
-  let translate_client = tauri::async_runtime::block_on(TranslateClient::new()).unwrap();
+  let mut translate_client = tauri::async_runtime::block_on(TranslateClient::new()).unwrap();
+  if let Err(e) = UserGlossary::apply_all(&mut translate_client) {
+    eprintln!("user glossary not loaded: {}", String::from(e));
+  }
+  match open_translation_cache() {
+    Ok(cache) => translate_client.set_cache(cache),
+    Err(e) => eprintln!("translation cache not loaded: {}", String::from(e)),
+  }
   let client = Arc::new(Mutex::new(translate_client));
   tauri::Builder::default()
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_fs::init())
     .plugin(tauri_plugin_shell::init())
     .manage(client)
-    .invoke_handler(tauri::generate_handler![process_excel_file])
+    .invoke_handler(tauri::generate_handler![
+      process_excel_file,
+      list_excel_sheets,
+      export_size_tables,
+      save_project,
+      load_project,
+      list_glossary_entries,
+      add_glossary_entry,
+      edit_glossary_entry,
+      delete_glossary_entry,
+      list_translation_cache,
+      export_translation_cache,
+      clear_translation_cache
+    ])
     .run(tauri::generate_context!())
     .expect("error while running tauri application");
 }
 
 pub enum Error {
   Tauri(tauri::Error),
-  ExcelRead,
-  EmptyFile,
-  InvalidSheetFormat,
-  InvalidItemCode,
-  InvalidSizeCode,
-  InvalidSizeText { error_line: String },
-  MelroseType(melrose_types::error::Error),
-  EmptySizeText,
-  Translation(phdb_translate::Error),
-  SystemIO(std::io::Error),
-  SerdeJson(serde_json::Error),
+  Core(size_table_core::Error),
 }
 
 impl From<tauri::Error> for Error {
@@ -47,27 +60,9 @@ impl From<tauri::Error> for Error {
   }
 }
 
-impl From<phdb_translate::Error> for Error {
-  fn from(e: phdb_translate::Error) -> Self {
-    Self::Translation(e)
-  }
-}
-
-impl From<std::io::Error> for Error {
-  fn from(e: std::io::Error) -> Self {
-    Self::SystemIO(e)
-  }
-}
-
-impl From<serde_json::Error> for Error {
-  fn from(e: serde_json::Error) -> Self {
-    Self::SerdeJson(e)
-  }
-}
-
-impl From<melrose_types::error::Error> for Error {
-  fn from(e: melrose_types::error::Error) -> Self {
-    Self::MelroseType(e)
+impl From<size_table_core::Error> for Error {
+  fn from(e: size_table_core::Error) -> Self {
+    Self::Core(e)
   }
 }
 
@@ -75,27 +70,7 @@ impl From<Error> for String {
   fn from(e: Error) -> Self {
     match e {
       Error::Tauri(_) => String::from("后台程序错误"),
-      Error::EmptyFile => String::from("文件是空文件"),
-      Error::ExcelRead => String::from("请选择需要打开的Excel文件"),
-      Error::InvalidSheetFormat => String::from("请确认Excel文件有[品番][採寸]和[SZ]列"),
-      Error::InvalidItemCode => String::from("品番格式错误"),
-      Error::InvalidSizeCode => String::from("SZ格式错误"),
-      Error::InvalidSizeText { error_line } => format!("採寸格式错误:{}", error_line),
-      Error::EmptySizeText => String::from("[採寸]列不能是空栏"),
-      Error::MelroseType(e) => match e {
-        melrose_types::error::Error::ItemCode(_) => String::from("品番格式错误"),
-        melrose_types::error::Error::SizeCode(_) => String::from("SZ格式错误"),
-        melrose_types::error::Error::ParseCommonError(e) => format!("格式错误:{e:?}"),
-        melrose_types::error::Error::ColorCode(_) => String::from("色番格式错误"),
-      },
-      Error::Translation(e) => format!("翻译失败:{e:?}"),
-      Error::SystemIO(_) => String::from("设定文件读取错误"),
-      Error::SerdeJson(e) => {
-        println!("error:{}", e);
-        String::from("设定文件解析错误")
-      }
+      Error::Core(e) => String::from(e),
     }
   }
 }
-
-pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,174 @@
+//! Headless entry point of the size table pipeline.
+//!
+//! Runs the same processing as the `process_excel_file` command without opening a window:
+//!
+//! ```sh
+//! stg ./size.xlsx ./out --format csv
+//! ```
+
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+use itertools::Itertools;
+use phdb_translate::{TargetLanguage, TranslateClient};
+use size_table_core::{
+  export::{export_item_metas, ExportFormat},
+  load_config, open_translation_cache, process_workbook,
+  units::{with_inches, InchMode},
+  SheetSelection, UserGlossary,
+};
+
+#[derive(Parser)]
+#[command(
+  name = "stg",
+  version,
+  about = "Generate size tables from an Excel file"
+)]
+struct Args {
+  /// the .xlsx, .xlsm, .xlsb, .xls, .ods or CSV file containing the [品番] [SZ] [採寸] columns
+  excel_path: PathBuf,
+  /// directory the generated tables are written to
+  out_dir: PathBuf,
+  /// output format: json, csv, png, jpg, svg, pdf, pdf-catalog, html, xlsx or xlsx-single
+  #[arg(short, long, default_value = "json", value_parser = parse_format)]
+  format: ExportFormat,
+  /// worksheet to process, repeat to process several (default: the first one)
+  #[arg(short, long = "sheet", value_name = "NAME")]
+  sheets: Vec<String>,
+  /// add inches: separate (an extra <code>_inch table) or combined ("cm / inch" cells)
+  #[arg(long, value_parser = parse_inch_mode)]
+  inch: Option<InchMode>,
+  /// process every worksheet of the workbook
+  #[arg(long, conflicts_with = "sheets")]
+  all_sheets: bool,
+  /// machine translate measurement names missing from the glossary
+  #[arg(long)]
+  machine_translate: bool,
+  /// language of the tables: zh, zh-TW, ko or en, repeat for several (default: config.json)
+  #[arg(long = "lang", value_name = "CODE", value_parser = parse_language)]
+  languages: Vec<TargetLanguage>,
+}
+
+fn parse_format(s: &str) -> Result<ExportFormat, String> {
+  s.parse().map_err(String::from)
+}
+
+fn parse_language(s: &str) -> Result<TargetLanguage, String> {
+  s.parse().map_err(|e: phdb_translate::Error| e.to_string())
+}
+
+fn parse_inch_mode(s: &str) -> Result<InchMode, String> {
+  match s {
+    "off" => Ok(InchMode::Off),
+    "separate" => Ok(InchMode::SeparateTable),
+    "combined" => Ok(InchMode::Combined),
+    other => Err(format!("unknown inch mode: {other}")),
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  let args = Args::parse();
+  match run(args).await {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(message) => {
+      eprintln!("{message}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+async fn run(args: Args) -> Result<(), String> {
+  let mut client = TranslateClient::new()
+    .await
+    .map_err(size_table_core::Error::Translation)?;
+  if let Err(e) = UserGlossary::apply_all(&mut client) {
+    eprintln!("user glossary not loaded: {}", String::from(e));
+  }
+  match open_translation_cache() {
+    Ok(cache) => client.set_cache(cache),
+    Err(e) => eprintln!("translation cache not loaded: {}", String::from(e)),
+  }
+  let config = load_config()?;
+  let mut options = config.process_options();
+  options.sheets = if args.all_sheets {
+    SheetSelection::All
+  } else if !args.sheets.is_empty() {
+    SheetSelection::Names(args.sheets.clone())
+  } else {
+    SheetSelection::First
+  };
+  options.machine_translation |= args.machine_translate;
+  if !args.languages.is_empty() {
+    options.languages = args.languages.clone();
+  }
+  if options.machine_translation {
+    client.set_translator(config.translator()?);
+  }
+  let output = process_workbook(&args.excel_path, &mut client, &options, |state| {
+    eprintln!("{state}");
+  })
+  .await?;
+  let mut inch = config.inch;
+  if let Some(mode) = args.inch {
+    inch.mode = mode;
+  }
+  let item_meta = with_inches(&output.item_meta, &inch);
+  let written = export_item_metas(&item_meta, &args.out_dir, args.format)?;
+  for path in written {
+    println!("{}", path.display());
+  }
+
+  let report = &output.report;
+  for duplicate in report.conflicts() {
+    eprintln!(
+      "{} {} SZ {}: 第{}行的採寸不一致，使用第{}行",
+      duplicate.sheet,
+      duplicate.item_code,
+      duplicate.size_code,
+      duplicate.rows.iter().join(", "),
+      duplicate.kept_row
+    );
+  }
+
+  for mismatch in &report.measurement_mismatches {
+    for missing in &mismatch.missing {
+      eprintln!(
+        "{} {} SZ {}: 缺少{}",
+        mismatch.sheet,
+        mismatch.item_code,
+        missing.size_code,
+        missing.names.iter().join(", ")
+      );
+    }
+  }
+
+  for untranslated in &output.untranslated {
+    let result = match &untranslated.machine_translation {
+      Some(translation) => format!("机器翻译为「{translation}」"),
+      None => String::from("未翻译"),
+    };
+    eprintln!(
+      "[{}] 词汇表中没有「{}」，{result} ({})",
+      untranslated.language,
+      untranslated.term,
+      untranslated.item_codes.iter().join(", ")
+    );
+  }
+
+  // the valid tables are written anyway, the exit code still tells scripts about bad rows
+  let issues = &report.issues;
+  for issue in issues {
+    eprintln!(
+      "{}!{}{}: {} ({})",
+      issue.sheet, issue.column_name, issue.row, issue.message, issue.value
+    );
+  }
+  if !issues.is_empty() {
+    return Err(format!(
+      "{}个单元格有错误，相关品番未生成尺码表",
+      issues.len()
+    ));
+  }
+  Ok(())
+}
@@ -0,0 +1,34 @@
+import React from "react";
+import styled from "styled-components";
+import { useAtom } from "jotai";
+import { Color } from "../../styles/Color";
+import ColumnFlex from "../../styles/styleAtoms/ColumnFlexWrapper";
+import RowFlex from "../../styles/styleAtoms/RowFlexWrapper";
+import { Button } from "./Button";
+import { showGlossaryAtom } from "../../lib/store";
+
+export const EditGlossary = () => {
+  const [showGlossary, setShowGlossary] = useAtom(showGlossaryAtom);
+  return (
+    <Wrapper>
+      <RowWrapper>
+        <Button onClick={() => setShowGlossary(!showGlossary)}>
+          {showGlossary ? "关闭词汇表" : "编辑词汇表"}
+        </Button>
+      </RowWrapper>
+    </Wrapper>
+  );
+};
+
+const Wrapper = styled(ColumnFlex)`
+  width: 80%;
+  max-width: 300px;
+  border: 0px solid;
+  border-radius: 30px;
+  background-color: ${Color.SUB};
+  min-height: 60px;
+`;
+
+const RowWrapper = styled(RowFlex)`
+  width: 100%;
+`;
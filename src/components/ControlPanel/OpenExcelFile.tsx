@@ -9,15 +9,20 @@ import {
   itemMetasAtom,
   showLoadingLogoAtom,
   statusInfoAtom,
+  untranslatedTermsAtom,
+  validationReportAtom,
 } from "../../lib/store";
 import { Button } from "./Button";
 import styled from "styled-components";
 import { Color } from "../../styles/Color";
 import { trimHomePath } from "../../lib/utility";
+import { LANGUAGES } from "../../lib/languages";
 import {
   CommandInvokeError,
   ProcessResponse,
   ProcessStatePayload,
+  SheetInfo,
+  TargetLanguage,
 } from "../../types";
 
 interface OpenExcelFileProps {
@@ -30,9 +35,18 @@ export const OpenExcelFile: React.FC<OpenExcelFileProps> = ({
   const [, setItemMetas] = useAtom(itemMetasAtom);
   const [, setShowLoadingLogo] = useAtom(showLoadingLogoAtom);
   const [, setStatusInfo] = useAtom(statusInfoAtom);
+  const [, setValidationReport] = useAtom(validationReportAtom);
+  const [, setUntranslatedTerms] = useAtom(untranslatedTermsAtom);
   const [excelPath, setExcelPath] = useState<string>("");
   const [filePath, setFilePath] = useState("");
   const [fileName, setFileName] = useState<string>("");
+  const [sheets, setSheets] = useState<SheetInfo[]>([]);
+  const [selectedSheets, setSelectedSheets] = useState<string[]>([]);
+  // null のときは config.json の設定に従う
+  const [machineTranslation, setMachineTranslation] = useState<
+    boolean | null
+  >(null);
+  const [languages, setLanguages] = useState<TargetLanguage[]>(["zh"]);
 
   const handleOpenFileOnClick = async () => {
     try {
@@ -48,9 +62,25 @@ export const OpenExcelFile: React.FC<OpenExcelFileProps> = ({
       const name = pathStr.split("/").pop() || "";
       setFileName(name);
 
+      // 読めないファイルは処理時にエラーを表示する
+      const sheetInfos = (await invoke("list_excel_sheets", {
+        excelPath: pathStr,
+      }).catch(() => [])) as SheetInfo[];
+      setSheets(sheetInfos);
+      // 既定ではデータのあるシートをすべて処理する
+      setSelectedSheets(
+        sheetInfos.filter((sheet) => sheet.rows > 0).map((sheet) => sheet.name)
+      );
+
       // 一度プレビューと結果をクリア
       onExcelLoaded([]);
       setItemMetas([]);
+      setUntranslatedTerms([]);
+      setValidationReport({
+        issues: [],
+        duplicates: [],
+        measurement_mismatches: [],
+      });
       setStatusInfo({ type: "normal", content: "done" });
     } catch (e) {
       setStatusInfo({ type: "error", content: "プレビュー取得失敗" });
@@ -65,6 +95,14 @@ export const OpenExcelFile: React.FC<OpenExcelFileProps> = ({
       });
       return;
     }
+    if (sheets.length > 1 && selectedSheets.length === 0) {
+      setStatusInfo({ type: "error", content: "请选择工作表" });
+      return;
+    }
+    if (languages.length === 0) {
+      setStatusInfo({ type: "error", content: "请选择语言" });
+      return;
+    }
     setShowLoadingLogo(true);
     setStatusInfo({ type: "normal", content: "文件处理中" });
 
@@ -80,11 +118,52 @@ export const OpenExcelFile: React.FC<OpenExcelFileProps> = ({
 
       const res = (await invoke("process_excel_file", {
         excelPath: excelPath,
+        // シート順を保つ
+        sheets:
+          sheets.length > 1
+            ? {
+                names: sheets
+                  .map((sheet) => sheet.name)
+                  .filter((name) => selectedSheets.includes(name)),
+              }
+            : "first",
+        // 切り替えていなければ送らない
+        machineTranslation: machineTranslation ?? undefined,
+        // 一覧の順に、品番ごとに言語の表が並ぶ
+        languages: LANGUAGES.map((language) => language.code).filter((code) =>
+          languages.includes(code)
+        ),
       })) as ProcessResponse;
 
       setItemMetas(res.item_meta);
+      setValidationReport(res.report);
+      setUntranslatedTerms(res.untranslated);
       onExcelLoaded([]); // プレビューをクリア
-      setStatusInfo({ type: "normal", content: "done" });
+      const conflicts = res.report.duplicates.filter((d) => d.conflicting);
+      if (res.report.issues.length > 0) {
+        setStatusInfo({
+          type: "error",
+          content: `${res.report.issues.length}个单元格有错误`,
+        });
+      } else if (conflicts.length > 0) {
+        setStatusInfo({
+          type: "error",
+          content: `${conflicts.length}组重复行的採寸不一致`,
+        });
+      } else if (res.untranslated.length > 0) {
+        const machineTranslated = res.untranslated.filter(
+          (t) => t.machine_translation !== null
+        ).length;
+        setStatusInfo({
+          type: "error",
+          content:
+            machineTranslated === res.untranslated.length
+              ? `${machineTranslated}个採寸项目为机器翻译，请确认`
+              : `${res.untranslated.length - machineTranslated}个採寸项目未翻译`,
+        });
+      } else {
+        setStatusInfo({ type: "normal", content: "done" });
+      }
     } catch (e) {
       const message =
         (e as any)?.toString?.() ??
@@ -99,6 +178,31 @@ export const OpenExcelFile: React.FC<OpenExcelFileProps> = ({
     }
   };
 
+  const handleSheetOnClick = (
+    _event: React.MouseEvent<HTMLButtonElement>,
+    name?: string
+  ) => {
+    if (!name) return;
+    setSelectedSheets((selected) =>
+      selected.includes(name)
+        ? selected.filter((sheet) => sheet !== name)
+        : [...selected, name]
+    );
+  };
+
+  const handleLanguageOnClick = (
+    _event: React.MouseEvent<HTMLButtonElement>,
+    code?: string
+  ) => {
+    if (!code) return;
+    const language = code as TargetLanguage;
+    setLanguages((selected) =>
+      selected.includes(language)
+        ? selected.filter((l) => l !== language)
+        : [...selected, language]
+    );
+  };
+
   return (
     <Wrapper>
       <RowWrapper>
@@ -107,6 +211,46 @@ export const OpenExcelFile: React.FC<OpenExcelFileProps> = ({
       <RowWrapper>
         <Text>现正打开：{filePath}</Text>
       </RowWrapper>
+      {sheets.length > 1 && (
+        <SheetsWrapper>
+          {sheets.map((sheet) => (
+            <Button
+              key={sheet.name}
+              type="pill"
+              name={sheet.name}
+              selected={selectedSheets.includes(sheet.name)}
+              fontSize="0.8rem"
+              onClick={handleSheetOnClick}
+            >
+              {`${sheet.name} (${sheet.rows})`}
+            </Button>
+          ))}
+        </SheetsWrapper>
+      )}
+      <SheetsWrapper>
+        {LANGUAGES.map((language) => (
+          <Button
+            key={language.code}
+            type="pill"
+            name={language.code}
+            selected={languages.includes(language.code)}
+            fontSize="0.8rem"
+            onClick={handleLanguageOnClick}
+          >
+            {language.name}
+          </Button>
+        ))}
+      </SheetsWrapper>
+      <RowWrapper>
+        <Button
+          type="pill"
+          selected={machineTranslation === true}
+          fontSize="0.8rem"
+          onClick={() => setMachineTranslation(!machineTranslation)}
+        >
+          机器翻译未收录项目
+        </Button>
+      </RowWrapper>
       <RowWrapper>
         <Button onClick={handleProcessClick} disabled={!fileName}>
           生成開始
@@ -129,6 +273,11 @@ const RowWrapper = styled(RowFlex)`
   width: 100%;
   gap: 15px;
 `;
+const SheetsWrapper = styled(RowWrapper)`
+  flex-wrap: wrap;
+  gap: 5px;
+  padding: 5px 10px;
+`;
 const Text = styled.div`
   overflow-wrap: break-word;
   min-width: 70%;
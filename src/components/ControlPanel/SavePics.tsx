@@ -4,18 +4,44 @@ import { Color } from "../../styles/Color";
 import { Button } from "./Button";
 import RowFlex from "../../styles/styleAtoms/RowFlexWrapper";
 import ColumnFlex from "../../styles/styleAtoms/ColumnFlexWrapper";
-import { saveDirAtom, statusInfoAtom, tableIdsAtom } from "../../lib/store";
+import {
+  exportFormatAtom,
+  inchModeAtom,
+  itemMetasAtom,
+  saveDirAtom,
+  statusInfoAtom,
+} from "../../lib/store";
 import { useAtom } from "jotai";
-import { checkDirThenCreate, saveElementToPath } from "../../lib/saveFile";
+import { invoke } from "@tauri-apps/api/core";
 import { open as shellOpen } from "@tauri-apps/plugin-shell";
 import { triggerTempStatusPanel } from "../../lib/utility";
+import { ExportFormat, InchMode } from "../../types";
+
+const EXPORT_FORMATS: ExportFormat[] = [
+  "jpg",
+  "png",
+  "svg",
+  "pdf",
+  "pdf-catalog",
+  "html",
+  "xlsx",
+  "xlsx-single",
+];
+
+const INCH_MODES: { mode: InchMode; label: string }[] = [
+  { mode: "off", label: "CM" },
+  { mode: "separateTable", label: "CM + INCH 表" },
+  { mode: "combined", label: "CM / INCH" },
+];
 
 export const SavePics = () => {
   const [saveDir] = useAtom(saveDirAtom);
   const [, setStatusInfo] = useAtom(statusInfoAtom);
-  const [tableIds] = useAtom(tableIdsAtom);
+  const [itemMetas] = useAtom(itemMetasAtom);
+  const [exportFormat, setExportFormat] = useAtom(exportFormatAtom);
+  const [inchMode, setInchMode] = useAtom(inchModeAtom);
   const handleSaveTableButtonOnClick = async () => {
-    if (tableIds.length === 0) {
+    if (itemMetas.length === 0) {
       triggerTempStatusPanel(setStatusInfo, "error", "请先打开源文件");
       return;
     }
@@ -23,19 +49,52 @@ export const SavePics = () => {
       triggerTempStatusPanel(setStatusInfo, "error", "请设置保存文件夹");
       return;
     }
-    for (let i = 0; i < tableIds.length; i++) {
-      const id = tableIds[i];
-      const baseDir = `${saveDir}/${id}`;
-      const fileDir = `${baseDir}/size_${id}.jpg`;
-      await checkDirThenCreate(baseDir);
-      setStatusInfo({ type: "normal", content: `正在保存 size_${id}.jpg` });
-      await saveElementToPath(id, fileDir);
+    setStatusInfo({ type: "normal", content: "正在保存尺码表" });
+    try {
+      // Rust 側で描画して <saveDir>/<品番>/size_<品番>.<拡張子> に保存する
+      await invoke("export_size_tables", {
+        itemMeta: itemMetas,
+        saveDir,
+        format: exportFormat,
+        inchMode,
+      });
+    } catch (e) {
+      triggerTempStatusPanel(setStatusInfo, "error", `${e}`);
+      return;
     }
     setStatusInfo({ type: "normal", content: "done" });
     shellOpen(saveDir);
   };
   return (
     <Wrapper>
+      <FormatsWrapper>
+        {EXPORT_FORMATS.map((format) => (
+          <Button
+            key={format}
+            type="pill"
+            name={format}
+            selected={format === exportFormat}
+            fontSize="0.8rem"
+            onClick={() => setExportFormat(format)}
+          >
+            {format.toUpperCase()}
+          </Button>
+        ))}
+      </FormatsWrapper>
+      <FormatsWrapper>
+        {INCH_MODES.map(({ mode, label }) => (
+          <Button
+            key={mode}
+            type="pill"
+            name={mode}
+            selected={mode === inchMode}
+            fontSize="0.8rem"
+            onClick={() => setInchMode(mode)}
+          >
+            {label}
+          </Button>
+        ))}
+      </FormatsWrapper>
       <RowWrapper>
         <Button onClick={handleSaveTableButtonOnClick}>保存尺码表</Button>
       </RowWrapper>
@@ -55,3 +114,8 @@ const Wrapper = styled(ColumnFlex)`
 const RowWrapper = styled(RowFlex)`
   width: 100%;
 `;
+
+const FormatsWrapper = styled(RowWrapper)`
+  flex-wrap: wrap;
+  padding: 5px 10px;
+`;
@@ -0,0 +1,118 @@
+import React, { useEffect, useState } from "react";
+import styled from "styled-components";
+import { useAtom } from "jotai";
+import { invoke } from "@tauri-apps/api/core";
+import { save } from "@tauri-apps/plugin-dialog";
+import { Color } from "../../styles/Color";
+import ColumnFlex from "../../styles/styleAtoms/ColumnFlexWrapper";
+import RowFlex from "../../styles/styleAtoms/RowFlexWrapper";
+import { Button } from "./Button";
+import { statusInfoAtom } from "../../lib/store";
+import { triggerTempStatusPanel } from "../../lib/utility";
+import { CacheEntry } from "../../types";
+
+// 機械翻訳の結果はアプリのデータフォルダにキャッシュされ、同じ項目は再度送信されない
+export const TranslationCache = () => {
+  const [, setStatusInfo] = useAtom(statusInfoAtom);
+  const [entries, setEntries] = useState<CacheEntry[]>([]);
+  const [showEntries, setShowEntries] = useState(false);
+
+  const reload = async () => {
+    try {
+      setEntries(await invoke<CacheEntry[]>("list_translation_cache"));
+    } catch (e) {
+      triggerTempStatusPanel(setStatusInfo, "error", `${e}`);
+    }
+  };
+
+  useEffect(() => {
+    reload();
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, []);
+
+  const handleExportOnClick = async () => {
+    const exportPath = await save({
+      defaultPath: "translation_cache.csv",
+      filters: [{ name: "CSV", extensions: ["csv"] }],
+    });
+    if (!exportPath) return;
+    try {
+      await invoke("export_translation_cache", { exportPath });
+    } catch (e) {
+      triggerTempStatusPanel(setStatusInfo, "error", `${e}`);
+    }
+  };
+
+  const handleClearOnClick = async () => {
+    if (!window.confirm("确定要清除翻译缓存吗？")) return;
+    try {
+      await invoke("clear_translation_cache");
+    } catch (e) {
+      triggerTempStatusPanel(setStatusInfo, "error", `${e}`);
+    }
+    await reload();
+  };
+
+  return (
+    <Wrapper>
+      <RowWrapper>
+        <Text>翻译缓存：{entries.length}条</Text>
+      </RowWrapper>
+      <RowWrapper>
+        <Button
+          type="pill"
+          fontSize="0.8rem"
+          onClick={async () => {
+            await reload();
+            setShowEntries(!showEntries);
+          }}
+        >
+          {showEntries ? "收起" : "查看"}
+        </Button>
+        <Button type="pill" fontSize="0.8rem" onClick={handleExportOnClick}>
+          导出
+        </Button>
+        <Button type="pill" fontSize="0.8rem" onClick={handleClearOnClick}>
+          清除
+        </Button>
+      </RowWrapper>
+      {showEntries && (
+        <Entries>
+          {entries.map((entry) => (
+            <div
+              key={`${entry.text}-${entry.target_lang}-${entry.backend}-${entry.glossary_version}`}
+            >
+              {`${entry.text} → ${entry.translation} (${entry.backend})`}
+            </div>
+          ))}
+        </Entries>
+      )}
+    </Wrapper>
+  );
+};
+
+const Wrapper = styled(ColumnFlex)`
+  width: 80%;
+  max-width: 300px;
+  border: 0px solid;
+  border-radius: 30px;
+  background-color: ${Color.SUB};
+  min-height: 60px;
+  padding: 5px 0;
+`;
+
+const RowWrapper = styled(RowFlex)`
+  width: 100%;
+  gap: 5px;
+`;
+
+const Text = styled.div`
+  font-size: 0.8rem;
+`;
+
+const Entries = styled.div`
+  width: 90%;
+  max-height: 150px;
+  overflow-y: auto;
+  font-size: 0.7rem;
+`;
@@ -6,6 +6,8 @@ import Logo from "../../LOGOTEXTJPN256.png";
 import { OpenExcelFile } from "./OpenExcelFile";
 import { SelectSaveDir } from "./SelectSaveDir";
 import { SavePics } from "./SavePics";
+import { EditGlossary } from "./EditGlossary";
+import { TranslationCache } from "./TranslationCache";
 import { useAtom } from "jotai";
 import { appVersionAtom } from "../../lib/store";
 import { ExcelPreview } from "./ExcelPreview";
@@ -22,6 +24,8 @@ export const ControlPanel = () => {
         <OpenExcelFile onExcelLoaded={setExcelData} />
         <SelectSaveDir />
         <SavePics />
+        <EditGlossary />
+        <TranslationCache />
         <ExcelPreview data={excelData} />
       </Wrapper>
       <AppVersionWrapper>v{appVersion}</AppVersionWrapper>
@@ -32,7 +36,7 @@ export const ControlPanel = () => {
 const Wrapper = styled(WrapperBase)`
   height: 100%;
   width: 100%;
-  gap: 80px;
+  gap: 40px;
   border-left: 1px solid ${Color.EliamoGold};
 `;
 
@@ -0,0 +1,164 @@
+import { invoke } from "@tauri-apps/api/core";
+import { useAtom } from "jotai";
+import React, { useEffect, useState } from "react";
+import styled from "styled-components";
+import { statusInfoAtom } from "../../lib/store";
+import { triggerTempStatusPanel } from "../../lib/utility";
+import { LANGUAGES, languageOf } from "../../lib/languages";
+import { Color } from "../../styles/Color";
+import { GlossaryEntry, TargetLanguage } from "../../types";
+import { Button } from "../ControlPanel/Button";
+
+// 設定フォルダの glossary.csv (簡体字以外は glossary.ko.csv など) に保存されるユーザー辞書
+// 変更はすぐに Rust 側の翻訳に反映され、次の読み込みから使われる
+export const GlossaryEditor = () => {
+  const [, setStatusInfo] = useAtom(statusInfoAtom);
+  const [language, setLanguage] = useState<TargetLanguage>("zh");
+  const [entries, setEntries] = useState<GlossaryEntry[]>([]);
+  const [editing, setEditing] = useState<string | null>(null);
+  const [source, setSource] = useState("");
+  const [target, setTarget] = useState("");
+
+  useEffect(() => {
+    invoke<GlossaryEntry[]>("list_glossary_entries", { language })
+      .then(setEntries)
+      .catch((e) => triggerTempStatusPanel(setStatusInfo, "error", `${e}`));
+    setEditing(null);
+    setSource("");
+    setTarget("");
+  }, [language, setStatusInfo]);
+
+  const update = async (command: string, args: Record<string, string>) => {
+    try {
+      setEntries(
+        await invoke<GlossaryEntry[]>(command, { ...args, language })
+      );
+    } catch (e) {
+      triggerTempStatusPanel(setStatusInfo, "error", `${e}`);
+      return;
+    }
+    setEditing(null);
+    setSource("");
+    setTarget("");
+  };
+
+  const handleSaveOnClick = () =>
+    editing === null
+      ? update("add_glossary_entry", { source, target })
+      : update("edit_glossary_entry", {
+          source: editing,
+          newSource: source,
+          target,
+        });
+
+  const startEditing = (entry: GlossaryEntry) => {
+    setEditing(entry.source);
+    setSource(entry.source);
+    setTarget(entry.target);
+  };
+
+  return (
+    <Wrapper>
+      <Title>词汇表（优先于内置词汇表）</Title>
+      <Languages>
+        {LANGUAGES.map((l) => (
+          <Button
+            key={l.code}
+            type="pill"
+            name={l.code}
+            selected={l.code === language}
+            fontSize="0.7rem"
+            onClick={(_event, code) => setLanguage(code as TargetLanguage)}
+          >
+            {l.name}
+          </Button>
+        ))}
+      </Languages>
+      <Table>
+        <tbody>
+          {entries.map((entry) => (
+            <tr key={entry.source}>
+              <td>{entry.source}</td>
+              <td>{entry.target}</td>
+              <td>{entry.builtin ? `内置: ${entry.builtin}` : ""}</td>
+              <td>
+                <Button
+                  type="pill"
+                  fontSize="0.7rem"
+                  onClick={() => startEditing(entry)}
+                >
+                  编辑
+                </Button>
+                <Button
+                  type="pill"
+                  fontSize="0.7rem"
+                  onClick={() =>
+                    update("delete_glossary_entry", { source: entry.source })
+                  }
+                >
+                  删除
+                </Button>
+              </td>
+            </tr>
+          ))}
+          <tr>
+            <td>
+              <Input
+                placeholder="日文"
+                value={source}
+                onChange={(e) => setSource(e.target.value)}
+              />
+            </td>
+            <td>
+              <Input
+                placeholder={languageOf(language).name}
+                value={target}
+                onChange={(e) => setTarget(e.target.value)}
+              />
+            </td>
+            <td />
+            <td>
+              <Button type="pill" fontSize="0.7rem" onClick={handleSaveOnClick}>
+                {editing === null ? "添加" : "保存"}
+              </Button>
+            </td>
+          </tr>
+        </tbody>
+      </Table>
+    </Wrapper>
+  );
+};
+
+const Wrapper = styled.div`
+  width: 80%;
+  padding: 10px 20px;
+  border: 2px solid ${Color.EliamoGold};
+  border-radius: 15px;
+  background-color: white;
+`;
+
+const Title = styled.div`
+  color: ${Color.Black};
+  margin-bottom: 5px;
+`;
+
+const Languages = styled.div`
+  display: flex;
+  gap: 5px;
+  margin-bottom: 5px;
+`;
+
+const Table = styled.table`
+  width: 100%;
+  font-size: 0.8rem;
+  border-collapse: collapse;
+  & td {
+    padding: 2px 8px;
+    border-bottom: 1px solid ${Color.SUB};
+  }
+`;
+
+const Input = styled.input`
+  width: 100%;
+  font-size: 0.8rem;
+`;
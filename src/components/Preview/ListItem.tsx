@@ -1,5 +1,6 @@
 import React from "react";
 import styled from "styled-components";
+import { tableIdOf } from "../../lib/store";
 import { ItemMeta } from "../../types";
 import { SizeTable } from "./SizeTable";
 import CodeWrapper from "../../styles/styleAtoms/ColumnFlexWrapper";
@@ -8,7 +9,11 @@ export const ListItem = ({ itemMeta }: { itemMeta: ItemMeta }) => {
   return (
     <Wrapper>
       <CodeWrapper>商品号：{itemMeta.code}</CodeWrapper>
-      <SizeTable id={`${itemMeta.code}`} data={itemMeta.table} />
+      <SizeTable
+        id={tableIdOf(itemMeta)}
+        data={itemMeta.table}
+        language={itemMeta.language}
+      />
     </Wrapper>
   );
 };
@@ -1,17 +1,24 @@
 import { useAtom } from "jotai";
 import React from "react";
 import styled from "styled-components";
-import { itemMetasAtom } from "../../lib/store";
+import { itemMetasAtom, showGlossaryAtom, tableIdOf } from "../../lib/store";
 import Base from "../../styles/styleAtoms/ColumnFlexWrapper";
 import { ItemMeta } from "../../types";
 import { ListItem } from "./ListItem";
+import { ValidationReport } from "./ValidationReport";
+import { GlossaryEditor } from "./GlossaryEditor";
+import { UntranslatedTerms } from "./UntranslatedTerms";
 
 export const ListView = () => {
   const [itemMetas] = useAtom(itemMetasAtom);
+  const [showGlossary] = useAtom(showGlossaryAtom);
   return (
     <Wrapper>
+      {showGlossary && <GlossaryEditor />}
+      <ValidationReport />
+      <UntranslatedTerms />
       {itemMetas.map((itemMeta: ItemMeta) => (
-        <ListItem key={`${itemMeta.code}`} itemMeta={itemMeta} />
+        <ListItem key={tableIdOf(itemMeta)} itemMeta={itemMeta} />
       ))}
     </Wrapper>
   );
@@ -1,15 +1,24 @@
 import React from "react";
 import { Table } from "./Table";
-import { ItemTable } from "../../types";
+import { ItemTable, TargetLanguage } from "../../types";
+import { languageOf } from "../../lib/languages";
 import Base from "../../styles/styleAtoms/ColumnFlexWrapper";
 import { TableTitle } from "../../styles/styleAtoms/Table";
 import styled from "styled-components";
 
-export const SizeTable = ({ id, data }: { id: string; data: ItemTable }) => {
+export const SizeTable = ({
+  id,
+  data,
+  language,
+}: {
+  id: string;
+  data: ItemTable;
+  language?: TargetLanguage;
+}) => {
   console.log(data);
   return (
     <Wrapper id={id}>
-      <TableTitle>尺码表</TableTitle>
+      <TableTitle>{languageOf(language).tableTitle}</TableTitle>
       <Table>
         <Table.Head>
           <Table.TR>
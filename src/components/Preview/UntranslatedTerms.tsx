@@ -0,0 +1,103 @@
+import { invoke } from "@tauri-apps/api/core";
+import { useAtom } from "jotai";
+import React, { useState } from "react";
+import styled from "styled-components";
+import { statusInfoAtom, untranslatedTermsAtom } from "../../lib/store";
+import { triggerTempStatusPanel } from "../../lib/utility";
+import { languageOf } from "../../lib/languages";
+import { Color } from "../../styles/Color";
+import { UntranslatedTerm } from "../../types";
+import { Button } from "../ControlPanel/Button";
+
+// 辞書に無い採寸項目名。機械翻訳された訳はそのまま承認でき、
+// 訳を入力するとその言語のユーザー辞書に追加され、次の読み込みから辞書で翻訳される
+export const UntranslatedTerms = () => {
+  const [, setStatusInfo] = useAtom(statusInfoAtom);
+  const [terms, setTerms] = useAtom(untranslatedTermsAtom);
+  const [targets, setTargets] = useState<Record<string, string>>({});
+  if (terms.length === 0) return null;
+
+  const keyOf = (term: UntranslatedTerm) => `${term.language}:${term.term}`;
+  const targetOf = (term: UntranslatedTerm) =>
+    targets[keyOf(term)] ?? term.machine_translation ?? "";
+
+  const handleAddOnClick = async (term: UntranslatedTerm) => {
+    try {
+      await invoke("add_glossary_entry", {
+        source: term.term,
+        target: targetOf(term),
+        language: term.language,
+      });
+    } catch (e) {
+      triggerTempStatusPanel(setStatusInfo, "error", `${e}`);
+      return;
+    }
+    setTerms(terms.filter((t) => keyOf(t) !== keyOf(term)));
+  };
+
+  return (
+    <Wrapper>
+      <Title>以下採寸项目不在词汇表中（添加后重新打开文件生效）</Title>
+      <Table>
+        <tbody>
+          {terms.map((term) => (
+            <tr key={keyOf(term)}>
+              <td>{term.term}</td>
+              <td>{languageOf(term.language).name}</td>
+              <td>{term.item_codes.join(", ")}</td>
+              <td>
+                {term.machine_translation !== null ? "机器翻译" : "未翻译"}
+              </td>
+              <td>
+                <Input
+                  placeholder={languageOf(term.language).name}
+                  value={targetOf(term)}
+                  onChange={(e) =>
+                    setTargets({ ...targets, [keyOf(term)]: e.target.value })
+                  }
+                />
+              </td>
+              <td>
+                <Button
+                  type="pill"
+                  fontSize="0.7rem"
+                  onClick={() => handleAddOnClick(term)}
+                >
+                  {term.machine_translation !== null ? "接受" : "添加"}
+                </Button>
+              </td>
+            </tr>
+          ))}
+        </tbody>
+      </Table>
+    </Wrapper>
+  );
+};
+
+const Wrapper = styled.div`
+  width: 80%;
+  padding: 10px 20px;
+  border: 2px solid ${Color.MAIN};
+  border-radius: 15px;
+  background-color: white;
+`;
+
+const Title = styled.div`
+  color: ${Color.MAIN};
+  margin-bottom: 5px;
+`;
+
+const Table = styled.table`
+  width: 100%;
+  font-size: 0.8rem;
+  border-collapse: collapse;
+  & td {
+    padding: 2px 8px;
+    border-bottom: 1px solid ${Color.SUB};
+  }
+`;
+
+const Input = styled.input`
+  width: 100%;
+  font-size: 0.8rem;
+`;
@@ -0,0 +1,95 @@
+import { useAtom } from "jotai";
+import React from "react";
+import styled from "styled-components";
+import { validationReportAtom } from "../../lib/store";
+import { Color } from "../../styles/Color";
+
+// 読み込めなかったセル、採寸の食い違う重複行、サイズ間で揃っていない採寸項目の一覧
+// 完全に同じ重複行は無害なので表示しない
+export const ValidationReport = () => {
+  const [report] = useAtom(validationReportAtom);
+  const conflicts = report.duplicates.filter((d) => d.conflicting);
+  const mismatches = report.measurement_mismatches;
+  if (
+    report.issues.length === 0 &&
+    conflicts.length === 0 &&
+    mismatches.length === 0
+  )
+    return null;
+  return (
+    <Wrapper>
+      {report.issues.length > 0 && (
+        <Title>以下单元格有错误，相关品番未生成尺码表</Title>
+      )}
+      <Table>
+        <tbody>
+          {report.issues.map((issue) => (
+            <tr key={`${issue.sheet}!${issue.column_name}${issue.row}`}>
+              <td>{`${issue.sheet}!${issue.column_name}${issue.row}`}</td>
+              <td>{issue.value}</td>
+              <td>{issue.message}</td>
+            </tr>
+          ))}
+        </tbody>
+      </Table>
+      {conflicts.length > 0 && <Title>以下重复行的採寸不一致</Title>}
+      <Table>
+        <tbody>
+          {conflicts.map((duplicate) => (
+            <tr key={`${duplicate.sheet}!${duplicate.rows[0]}`}>
+              <td>
+                {`${duplicate.sheet} ${duplicate.item_code} SZ ${duplicate.size_code}`}
+              </td>
+              <td>
+                {duplicate.rows
+                  .map((row, i) => `${row}行: ${duplicate.values[i]}`)
+                  .join(" / ")}
+              </td>
+              <td>{`使用第${duplicate.kept_row}行`}</td>
+            </tr>
+          ))}
+        </tbody>
+      </Table>
+      {mismatches.length > 0 && <Title>以下尺码缺少部分採寸项目</Title>}
+      <Table>
+        <tbody>
+          {mismatches.flatMap((mismatch) =>
+            mismatch.missing.map((missing) => (
+              <tr
+                key={`${mismatch.sheet}!${mismatch.item_code}-${missing.size_code}`}
+              >
+                <td>
+                  {`${mismatch.sheet} ${mismatch.item_code} SZ ${missing.size_code}`}
+                </td>
+                <td>{missing.names.join(", ")}</td>
+              </tr>
+            ))
+          )}
+        </tbody>
+      </Table>
+    </Wrapper>
+  );
+};
+
+const Wrapper = styled.div`
+  width: 80%;
+  padding: 10px 20px;
+  border: 2px solid ${Color.MAIN};
+  border-radius: 15px;
+  background-color: white;
+`;
+
+const Title = styled.div`
+  color: ${Color.MAIN};
+  margin-bottom: 5px;
+`;
+
+const Table = styled.table`
+  width: 100%;
+  font-size: 0.8rem;
+  border-collapse: collapse;
+  & td {
+    padding: 2px 8px;
+    border-bottom: 1px solid ${Color.SUB};
+  }
+`;
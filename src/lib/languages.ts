@@ -0,0 +1,17 @@
+import { TargetLanguage } from "../types";
+
+// Rust 側 size_table_core::labels と同じ固定ラベル
+export const LANGUAGES: {
+  code: TargetLanguage;
+  name: string;
+  tableTitle: string;
+}[] = [
+  { code: "zh", name: "简体中文", tableTitle: "尺码表" },
+  { code: "zh-TW", name: "繁體中文", tableTitle: "尺碼表" },
+  { code: "ko", name: "한국어", tableTitle: "사이즈표" },
+  { code: "en", name: "English", tableTitle: "Size Chart" },
+];
+
+export function languageOf(code: TargetLanguage | undefined) {
+  return LANGUAGES.find((language) => language.code === code) ?? LANGUAGES[0];
+}
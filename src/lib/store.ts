@@ -1,14 +1,39 @@
 import { atom } from "jotai";
-import { ItemMeta, StatusInfo } from "../types";
+import {
+  ExportFormat,
+  InchMode,
+  ItemMeta,
+  StatusInfo,
+  UntranslatedTerm,
+  ValidationReport,
+} from "../types";
 
 export const itemMetasAtom = atom<ItemMeta[]>([]);
 
+export const validationReportAtom = atom<ValidationReport>({
+  issues: [],
+  duplicates: [],
+  measurement_mismatches: [],
+});
+
+export const untranslatedTermsAtom = atom<UntranslatedTerm[]>([]);
+
+// the same item code may be on several sheets, in several languages
+export const tableIdOf = (itemMeta: ItemMeta) =>
+  `${itemMeta.sheet}/${itemMeta.code}/${itemMeta.language}`;
+
 export const tableIdsAtom = atom<string[]>((get) =>
-  get(itemMetasAtom).map((itemMeta) => `${itemMeta.code}`)
+  get(itemMetasAtom).map(tableIdOf)
 );
 
 export const saveDirAtom = atom<string>("");
 
+export const exportFormatAtom = atom<ExportFormat>("jpg");
+
+export const inchModeAtom = atom<InchMode>("off");
+
+export const showGlossaryAtom = atom<boolean>(false);
+
 export const statusInfoAtom = atom<StatusInfo>({
   type: "normal",
   content: "done",
@@ -1,18 +1,147 @@
 export interface ProcessResponse {
   item_meta: ItemMeta[];
+  report: ValidationReport;
+  untranslated: UntranslatedTerm[];
+}
+
+// 尺码表の言語
+export type TargetLanguage = "zh" | "zh-TW" | "ko" | "en";
+
+// 辞書に無い採寸項目名、機械翻訳されていなければ日本語のまま
+export interface UntranslatedTerm {
+  term: string;
+  // 辞書に無かった言語
+  language: TargetLanguage;
+  item_codes: string[];
+  machine_translation: string | null;
+}
+
+export type IssueKind =
+  | "invalidItemCode"
+  | "invalidSizeCode"
+  | "invalidSizeText"
+  | "emptySizeText";
+
+export interface RowIssue {
+  sheet: string;
+  row: number;
+  column: "ItemCode" | "SizeCode" | "SizeText";
+  column_name: string;
+  value: string;
+  kind: IssueKind;
+  message: string;
+}
+
+export interface DuplicateRows {
+  sheet: string;
+  item_code: string;
+  size_code: string;
+  rows: number[];
+  values: string[];
+  // false when every row has the same 採寸
+  conflicting: boolean;
+  kept_row: number;
+}
+
+export interface MissingMeasurements {
+  size_code: string;
+  names: string[];
+}
+
+export interface MeasurementMismatch {
+  sheet: string;
+  item_code: string;
+  missing: MissingMeasurements[];
+}
+
+export interface ValidationReport {
+  issues: RowIssue[];
+  duplicates: DuplicateRows[];
+  // the tables are still generated, with the missing cells marked "-"
+  measurement_mismatches: MeasurementMismatch[];
 }
 
 export interface ItemMeta {
   code: string;
   size_code: string;
+  // worksheet the rows were read from
+  sheet: string;
+  language: TargetLanguage;
+  // lengths in inches, only in the extra tables of the separate inch export
+  inch: boolean;
   table: ItemTable;
 }
 
 export interface ItemTable {
   head: string[];
   body: string[][];
+  // 列ごとに長さかどうか、単位のない値のインチ換算に使う
+  lengths: boolean[];
+}
+
+export interface SheetInfo {
+  name: string;
+  // rows below the header
+  rows: number;
+}
+
+export type SheetSelection = "first" | "all" | { names: string[] };
+
+export interface CellEdit {
+  // empty in projects saved before the sheet was recorded
+  sheet: string;
+  code: string;
+  language: TargetLanguage;
+  // 0 is the head row, 1.. are the body rows
+  row: number;
+  column: number;
+  value: string;
 }
 
+export interface ProjectFile {
+  version: number;
+  source: { path: string; sha256: string };
+  glossary_version: string;
+  item_meta: ItemMeta[];
+  edits: CellEdit[];
+}
+
+export interface ProjectResponse {
+  project: ProjectFile;
+  item_meta: ItemMeta[];
+  source_changed: boolean;
+  glossary_changed: boolean;
+}
+
+export type ExportFormat =
+  | "jpg"
+  | "png"
+  | "svg"
+  | "pdf"
+  | "pdf-catalog"
+  | "html"
+  | "xlsx"
+  | "xlsx-single";
+
+export interface GlossaryEntry {
+  source: string;
+  target: string;
+  // 上書きしている組み込み辞書の訳
+  builtin: string | null;
+}
+
+// 機械翻訳キャッシュの1件
+export interface CacheEntry {
+  text: string;
+  source_lang: string;
+  target_lang: string;
+  backend: string;
+  glossary_version: string;
+  translation: string;
+}
+
+export type InchMode = "off" | "separateTable" | "combined";
+
 export type StatusInfoType = "normal" | "error";
 
 export type StatusInfoContent = "文件处理中" | "翻译中" | "done" | string;